@@ -0,0 +1,273 @@
+//! # Participation Badges
+//!
+//! A meta-rewards collection minting soulbound "badge" tokens to accounts
+//! that claim fragments across multiple distinct rounds. Participation is
+//! reported by authorized reporter contracts (normally the round registry
+//! or reputation contract); once an account's distinct-round streak
+//! crosses a threshold it can claim its badge, and higher streaks upgrade
+//! the badge's tier. Badges are bound to the account and cannot be
+//! transferred.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod badges {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use ownable::{Ownable, OwnableError, OwnershipData};
+
+    /// Tier of a badge: the number of streak thresholds met.
+    pub type BadgeTier = u8;
+
+    #[ink(storage)]
+    pub struct Badges {
+        /// Ownership of the collection.
+        ownership: OwnershipData,
+        /// Accounts allowed to report participation (registry or
+        /// reputation contracts).
+        reporters: Mapping<AccountId, ()>,
+        /// Deduplication of `(account, round)` participation reports.
+        participated: Mapping<(AccountId, AccountId), ()>,
+        /// Number of distinct rounds each account has participated in.
+        round_counts: Mapping<AccountId, u32>,
+        /// The badge tier held per account, once claimed.
+        badges: Mapping<AccountId, BadgeTier>,
+        /// Ascending distinct-round thresholds; meeting the n-th threshold
+        /// earns tier n+1.
+        thresholds: Vec<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The caller is not the collection owner.
+        NotOwner,
+        /// The caller is not an authorized reporter.
+        NotReporter,
+        /// The caller's streak does not reach the first threshold.
+        NoBadgeEarned,
+        /// The caller's badge is already at the earned tier.
+        AlreadyAtTier,
+    }
+
+    /// Emitted when an account's participation in a new round is recorded.
+    #[ink(event)]
+    pub struct ParticipationRecorded {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        round: AccountId,
+        rounds_participated: u32,
+    }
+
+    /// Emitted when a badge is minted or upgraded.
+    #[ink(event)]
+    pub struct BadgeAwarded {
+        #[ink(topic)]
+        account: AccountId,
+        tier: BadgeTier,
+    }
+
+    impl Badges {
+        /// Creates a badge collection owned by the caller. `thresholds`
+        /// are the ascending distinct-round counts earning each tier.
+        #[ink(constructor)]
+        pub fn new(thresholds: Vec<u32>) -> Self {
+            Self {
+                ownership: OwnershipData::new(Self::env().caller()),
+                reporters: Mapping::default(),
+                participated: Mapping::default(),
+                round_counts: Mapping::default(),
+                badges: Mapping::default(),
+                thresholds,
+            }
+        }
+
+        /// Authorizes `reporter` to record participation.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn authorize_reporter(&mut self, reporter: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.reporters.insert(reporter, &());
+            Ok(())
+        }
+
+        /// Revokes `reporter`'s authorization.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn revoke_reporter(&mut self, reporter: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.reporters.remove(reporter);
+            Ok(())
+        }
+
+        /// Returns `true` if `account` may record participation.
+        #[ink(message)]
+        pub fn is_reporter(&self, account: AccountId) -> bool {
+            self.reporters.contains(account)
+        }
+
+        /// Records that `account` claimed at least one fragment in
+        /// `round`. Repeated reports for the same round are ignored.
+        ///
+        /// Only callable by authorized reporters.
+        #[ink(message)]
+        pub fn record_participation(
+            &mut self,
+            account: AccountId,
+            round: AccountId,
+        ) -> Result<(), Error> {
+            if !self.reporters.contains(self.env().caller()) {
+                return Err(Error::NotReporter);
+            }
+            if self.participated.contains((account, round)) {
+                return Ok(());
+            }
+            self.participated.insert((account, round), &());
+            let rounds_participated = self
+                .round_counts
+                .get(account)
+                .unwrap_or(0)
+                .saturating_add(1);
+            self.round_counts.insert(account, &rounds_participated);
+            self.env().emit_event(ParticipationRecorded {
+                account,
+                round,
+                rounds_participated,
+            });
+            Ok(())
+        }
+
+        /// Returns the number of distinct rounds `account` has
+        /// participated in.
+        #[ink(message)]
+        pub fn rounds_participated(&self, account: AccountId) -> u32 {
+            self.round_counts.get(account).unwrap_or(0)
+        }
+
+        /// Returns the badge tier `account`'s streak currently earns,
+        /// whether or not it has been claimed.
+        #[ink(message)]
+        pub fn earned_tier(&self, account: AccountId) -> BadgeTier {
+            let count = self.round_counts.get(account).unwrap_or(0);
+            self.thresholds
+                .iter()
+                .filter(|threshold| count >= **threshold)
+                .count() as BadgeTier
+        }
+
+        /// Mints the caller's badge, or upgrades it to the tier their
+        /// streak has earned.
+        #[ink(message)]
+        pub fn claim_badge(&mut self) -> Result<BadgeTier, Error> {
+            let caller = self.env().caller();
+            let earned = self.earned_tier(caller);
+            if earned == 0 {
+                return Err(Error::NoBadgeEarned);
+            }
+            if self.badges.get(caller).unwrap_or(0) >= earned {
+                return Err(Error::AlreadyAtTier);
+            }
+            self.badges.insert(caller, &earned);
+            self.env().emit_event(BadgeAwarded {
+                account: caller,
+                tier: earned,
+            });
+            Ok(earned)
+        }
+
+        /// Returns the badge tier held by `account`, if one was claimed.
+        #[ink(message)]
+        pub fn badge_of(&self, account: AccountId) -> Option<BadgeTier> {
+            self.badges.get(account)
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)
+        }
+    }
+
+    impl Ownable for Badges {
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            self.ownership.owner()
+        }
+
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+            let event = self.ownership.transfer(self.env().caller(), new_owner)?;
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        fn collection() -> Badges {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut badges = Badges::new(ink::prelude::vec![2, 5]);
+            badges
+                .authorize_reporter(accounts.django)
+                .expect("alice owns the collection");
+            badges
+        }
+
+        #[ink::test]
+        fn participation_is_deduplicated_per_round() {
+            let accounts = accounts();
+            let mut badges = collection();
+            set_caller(accounts.bob);
+            assert_eq!(
+                badges.record_participation(accounts.bob, accounts.eve),
+                Err(Error::NotReporter)
+            );
+            set_caller(accounts.django);
+            assert!(badges.record_participation(accounts.bob, accounts.eve).is_ok());
+            assert!(badges.record_participation(accounts.bob, accounts.eve).is_ok());
+            assert_eq!(badges.rounds_participated(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn badges_mint_and_upgrade_with_streaks() {
+            let accounts = accounts();
+            let mut badges = collection();
+            set_caller(accounts.bob);
+            assert_eq!(badges.claim_badge(), Err(Error::NoBadgeEarned));
+
+            set_caller(accounts.django);
+            for round in [accounts.alice, accounts.eve] {
+                badges
+                    .record_participation(accounts.bob, round)
+                    .expect("django reports");
+            }
+            set_caller(accounts.bob);
+            assert_eq!(badges.earned_tier(accounts.bob), 1);
+            assert_eq!(badges.claim_badge(), Ok(1));
+            assert_eq!(badges.claim_badge(), Err(Error::AlreadyAtTier));
+            assert_eq!(badges.badge_of(accounts.bob), Some(1));
+
+            set_caller(accounts.django);
+            for round in [accounts.charlie, accounts.frank, accounts.django] {
+                badges
+                    .record_participation(accounts.bob, round)
+                    .expect("django reports");
+            }
+            set_caller(accounts.bob);
+            assert_eq!(badges.claim_badge(), Ok(2));
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! # Acknowledgement Bounty
+//!
+//! A bounty pool paying out to reporters of invalid acknowledgements.
+//! Anyone can post evidence that an FA NFT is bogus (for example a proof
+//! mismatch discovered after an upgrade); if the arbiter confirms the
+//! report, the reporter is paid from the pool and the token is revoked
+//! cross-contract through the `Burnable` interface. The revocation call is
+//! best-effort: the collection must have authorized this contract (token
+//! or operator approval), and a failed burn is surfaced in the resolution
+//! event rather than blocking the payout.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod bounty {
+    use burnable::{Burnable, TokenId};
+    use ink::codegen::TraitCallBuilder;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use ownable::{Ownable, OwnableError, OwnershipData};
+
+    /// Identifier of a posted report.
+    pub type ReportId = u32;
+
+    /// Lifecycle of a report.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ReportStatus {
+        /// Posted and awaiting the arbiter's decision.
+        Pending,
+        /// Confirmed; `revoked` records whether the cross-contract burn
+        /// succeeded.
+        Confirmed { revoked: bool },
+        /// Dismissed by the arbiter.
+        Dismissed,
+    }
+
+    /// A report that an acknowledgement token is invalid.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Report {
+        /// The account that posted the report.
+        pub reporter: AccountId,
+        /// The challenged token.
+        pub token_id: TokenId,
+        /// The token's holder, needed for the revocation call.
+        pub holder: AccountId,
+        /// The block at which the report was posted.
+        pub posted_at: BlockNumber,
+        /// Current status of the report.
+        pub status: ReportStatus,
+    }
+
+    #[ink(storage)]
+    pub struct Bounty {
+        /// Ownership of the bounty pool; the owner arbitrates reports.
+        ownership: OwnershipData,
+        /// The acknowledgement NFT collection reports are filed against.
+        fa_nft: AccountId,
+        /// Amount paid per confirmed report.
+        bounty_amount: Balance,
+        /// Posted reports by id.
+        reports: Mapping<ReportId, Report>,
+        /// The id the next posted report receives.
+        next_report_id: ReportId,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The caller is not the arbiter.
+        NotArbiter,
+        /// No report with the given id exists.
+        UnknownReport,
+        /// The report has already been resolved.
+        ReportAlreadyResolved,
+        /// The pool cannot cover the bounty without being reaped.
+        InsufficientPool,
+        /// The native token transfer failed.
+        TransferFailed,
+    }
+
+    /// Emitted when a report is posted. The evidence bytes are surfaced
+    /// for the arbiter and off-chain indexers.
+    #[ink(event)]
+    pub struct ReportPosted {
+        #[ink(topic)]
+        report_id: ReportId,
+        #[ink(topic)]
+        reporter: AccountId,
+        token_id: TokenId,
+        evidence: Vec<u8>,
+    }
+
+    /// Emitted when the arbiter resolves a report.
+    #[ink(event)]
+    pub struct ReportResolved {
+        #[ink(topic)]
+        report_id: ReportId,
+        confirmed: bool,
+        /// Whether the cross-contract revocation succeeded.
+        revoked: bool,
+        paid: Balance,
+    }
+
+    impl Bounty {
+        /// Creates a bounty pool over the given collection, owned (and
+        /// arbitrated) by the caller. The transferred balance seeds the
+        /// pool; each confirmed report pays `bounty_amount`.
+        #[ink(constructor, payable)]
+        pub fn new(fa_nft: AccountId, bounty_amount: Balance) -> Self {
+            Self {
+                ownership: OwnershipData::new(Self::env().caller()),
+                fa_nft,
+                bounty_amount,
+                reports: Mapping::default(),
+                next_report_id: 0,
+            }
+        }
+
+        /// Posts a report that `token_id`, held by `holder`, is an invalid
+        /// acknowledgement, attaching `evidence` for the arbiter.
+        #[ink(message)]
+        pub fn post_report(
+            &mut self,
+            token_id: TokenId,
+            holder: AccountId,
+            evidence: Vec<u8>,
+        ) -> ReportId {
+            let reporter = self.env().caller();
+            let report_id = self.next_report_id;
+            self.next_report_id = report_id.saturating_add(1);
+            self.reports.insert(
+                report_id,
+                &Report {
+                    reporter,
+                    token_id,
+                    holder,
+                    posted_at: self.env().block_number(),
+                    status: ReportStatus::Pending,
+                },
+            );
+            self.env().emit_event(ReportPosted {
+                report_id,
+                reporter,
+                token_id,
+                evidence,
+            });
+            report_id
+        }
+
+        /// Resolves a pending report. On confirmation the reporter is paid
+        /// the bounty and the token's revocation is attempted through the
+        /// collection's `Burnable` interface; a failed burn (for example a
+        /// missing approval) is recorded in the event but does not undo
+        /// the payout.
+        ///
+        /// Only callable by the arbiter.
+        #[ink(message)]
+        pub fn resolve_report(&mut self, report_id: ReportId, confirm: bool) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotArbiter)?;
+            let mut report = self.reports.get(report_id).ok_or(Error::UnknownReport)?;
+            if report.status != ReportStatus::Pending {
+                return Err(Error::ReportAlreadyResolved);
+            }
+            let mut paid = 0;
+            let mut revoked = false;
+            if confirm {
+                let available = self
+                    .env()
+                    .balance()
+                    .saturating_sub(self.env().minimum_balance());
+                if self.bounty_amount > available {
+                    return Err(Error::InsufficientPool);
+                }
+                self.env()
+                    .transfer(report.reporter, self.bounty_amount)
+                    .map_err(|_| Error::TransferFailed)?;
+                paid = self.bounty_amount;
+                revoked = self.try_revoke(report.holder, report.token_id);
+                report.status = ReportStatus::Confirmed { revoked };
+            } else {
+                report.status = ReportStatus::Dismissed;
+            }
+            self.reports.insert(report_id, &report);
+            self.env().emit_event(ReportResolved {
+                report_id,
+                confirmed: confirm,
+                revoked,
+                paid,
+            });
+            Ok(())
+        }
+
+        /// Returns the report posted under `report_id`, if any.
+        #[ink(message)]
+        pub fn get_report(&self, report_id: ReportId) -> Option<Report> {
+            self.reports.get(report_id)
+        }
+
+        /// Returns the collection this pool arbitrates.
+        #[ink(message)]
+        pub fn get_fa_nft(&self) -> AccountId {
+            self.fa_nft
+        }
+
+        /// Tops the pool up with the transferred value.
+        #[ink(message, payable)]
+        pub fn fund(&mut self) {}
+
+        /// Attempts the cross-contract burn of the confirmed token,
+        /// reporting success without propagating failures.
+        fn try_revoke(&self, holder: AccountId, token_id: TokenId) -> bool {
+            let mut nft: ink::contract_ref!(Burnable) = self.fa_nft.into();
+            matches!(
+                nft.call_mut().burn_from(holder, token_id).try_invoke(),
+                Ok(Ok(Ok(())))
+            )
+        }
+    }
+
+    impl Ownable for Bounty {
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            self.ownership.owner()
+        }
+
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+            let event = self.ownership.transfer(self.env().caller(), new_owner)?;
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        #[ink::test]
+        fn posting_and_dismissing_reports() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut bounty = Bounty::new(accounts.django, 100);
+            set_caller(accounts.bob);
+            let report_id = bounty.post_report(7, accounts.charlie, b"mismatch".to_vec());
+            let report = bounty.get_report(report_id).expect("report exists");
+            assert_eq!(report.reporter, accounts.bob);
+            assert_eq!(report.status, ReportStatus::Pending);
+
+            // only the arbiter resolves; dismissal pays nothing and leaves
+            // the token alone
+            assert_eq!(bounty.resolve_report(report_id, false), Err(Error::NotArbiter));
+            set_caller(accounts.alice);
+            assert!(bounty.resolve_report(report_id, false).is_ok());
+            assert_eq!(
+                bounty.get_report(report_id).map(|report| report.status),
+                Some(ReportStatus::Dismissed)
+            );
+            assert_eq!(
+                bounty.resolve_report(report_id, true),
+                Err(Error::ReportAlreadyResolved)
+            );
+            assert_eq!(bounty.resolve_report(9, true), Err(Error::UnknownReport));
+        }
+
+        #[ink::test]
+        fn confirmation_requires_a_funded_pool() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut bounty = Bounty::new(accounts.django, 1_000_000_000);
+            let report_id = bounty.post_report(7, accounts.charlie, Vec::new());
+            // the pool balance cannot cover the bounty
+            assert_eq!(
+                bounty.resolve_report(report_id, true),
+                Err(Error::InsufficientPool)
+            );
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Known `(cid, account, block) -> token id` vectors for the derivation
+//! in `FaNft::derive_token_id`.
+//!
+//! Off-chain implementations (indexers in JS, Python, ...) re-derive
+//! token ids from mint inputs instead of reading them back from chain
+//! state. These vectors pin the derivation — keccak-256 over the
+//! SCALE-encoded `(cid, account, block)` tuple, first four digest bytes
+//! read little-endian — so a reimplementation can be verified in plain
+//! unit tests, without a node or the contract. The contract's own tests
+//! assert `derive_token_id` reproduces every vector, keeping the two in
+//! lock-step.
+
+/// One known input/output pair of the token id derivation.
+pub struct TokenIdVector {
+    /// The fragment cid, as raw bytes.
+    pub cid: &'static [u8],
+    /// The raw 32 bytes of the recipient account.
+    pub account: [u8; 32],
+    /// The block number the token is minted at.
+    pub block: u32,
+    /// The token id the derivation must produce.
+    pub token_id: u32,
+}
+
+/// The published derivation vectors. Each input dimension varies at
+/// least once, so a reimplementation that drops or reorders an input
+/// fails on some vector.
+pub const TOKEN_ID_VECTORS: &[TokenIdVector] = &[
+    TokenIdVector {
+        cid: &[0x01, 0x55, 0x01],
+        account: [0x01; 32],
+        block: 0,
+        token_id: 2_585_583_553,
+    },
+    // same cid and account, one block later
+    TokenIdVector {
+        cid: &[0x01, 0x55, 0x01],
+        account: [0x01; 32],
+        block: 1,
+        token_id: 997_038_277,
+    },
+    // same account and block, different cid
+    TokenIdVector {
+        cid: &[0x01, 0x55, 0x02],
+        account: [0x01; 32],
+        block: 1,
+        token_id: 4_007_940_415,
+    },
+    // same cid and block, different account
+    TokenIdVector {
+        cid: &[0x01, 0x55, 0x02],
+        account: [0x02; 32],
+        block: 1,
+        token_id: 2_233_080_821,
+    },
+    TokenIdVector {
+        cid: &[0x01, 0x70, 0xde, 0xad, 0xbe, 0xef],
+        account: [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b,
+            0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        block: 7_777_777,
+        token_id: 4_167_645_393,
+    },
+    // single-byte cid at the largest block number
+    TokenIdVector {
+        cid: &[0x01],
+        account: [0xff; 32],
+        block: u32::MAX,
+        token_id: 2_277_416_799,
+    },
+];
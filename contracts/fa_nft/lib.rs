@@ -0,0 +1,2954 @@
+//! # Fragment Acknowledgement NFT
+//!
+//! A non-fungible token representing the acknowledgement of a fragment of
+//! data. Each token is minted by an authorized minter (normally a
+//! `FragmentsRound` contract) when an account proves possession of a
+//! fragment, and carries a [`fa_nft::FragmentAcknowledgement`] record binding
+//! the fragment, the claimer, and the block at which the claim was accepted.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub mod fixtures;
+
+#[ink::contract]
+pub mod fa_nft {
+    use acknowledgeable::Acknowledgeable;
+    use burnable::{BurnError, Burnable};
+    use enumerable::Enumerable;
+    use guardian::{Guarded, GuardianData, GuardianError};
+    use ink::codegen::TraitCallBuilder;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::{Mapping, StorageVec};
+    use mintable::{MintError, Mintable};
+    use ownable::{Ownable, OwnableError, OwnershipData};
+    use admin_log::{AdminLogData, AdminLogPage};
+    use ownable2step::Ownable2Step;
+    use scale::Encode;
+    use timelock::{ActionId, Scheduled, TimelockData};
+    use transfer_hook::TransferHook;
+    use treasury::{FeeSource, TreasuryData};
+
+    /// Identifier of a fragment's content — raw multihash/CIDv1 bytes,
+    /// bounded here by [`FaNft::MAX_CID_LENGTH`] — and the token id
+    /// namespace, shared with the rounds through the `fragments-types`
+    /// crate.
+    use fragments_types::{CallBudget, ContractInfo, FragmentMeta};
+    pub use fragments_types::{FragmentCid, TokenId};
+
+    pub use acknowledgeable::FragmentAcknowledgement;
+
+    /// A time-bounded delegation of a token's "active custodian" role,
+    /// distinct from transfer approvals: the delegate stores and serves the
+    /// fragment (and can be directed its streaming rewards) while the owner
+    /// retains the token.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Delegation {
+        /// The operator acting as custodian.
+        pub operator: AccountId,
+        /// The last block at which the delegation is active.
+        pub until_block: BlockNumber,
+    }
+
+    /// An ERC-4907-style usage grant: `user` may use the token (e.g.
+    /// access the underlying fragment retrieval service) until `expires`,
+    /// without receiving any transfer rights. Cleared when the token
+    /// moves.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct UserInfo {
+        /// The account granted usage rights.
+        pub user: AccountId,
+        /// The last block at which the grant is active.
+        pub expires: BlockNumber,
+    }
+
+    /// One entry in a token's bounded provenance: the account that held
+    /// the token and the block it acquired it at.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ProvenanceEntry {
+        /// The account that acquired the token.
+        pub holder: AccountId,
+        /// The block at which it acquired the token.
+        pub since: BlockNumber,
+    }
+
+    /// The record carried by a summary token minted by
+    /// [`FaNft::consolidate`]: the set of fragments the burned
+    /// acknowledgements covered and the earliest block any of them was
+    /// claimed at.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ConsolidatedSummary {
+        /// The distinct fragment cids covered by the summary.
+        pub cids: Vec<FragmentCid>,
+        /// The earliest claim block among the merged acknowledgements.
+        pub earliest_block: BlockNumber,
+        /// Number of acknowledgements merged in, transitively.
+        pub merged: u32,
+    }
+
+    /// The findings of [`FaNft::check_invariants`] over one page of the
+    /// token enumeration. All vectors empty means the page is consistent.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct InvariantReport {
+        /// Number of enumeration slots inspected.
+        pub tokens_checked: u32,
+        /// The offset to continue from, or `None` when the enumeration has
+        /// been fully walked.
+        pub next_offset: Option<u32>,
+        /// Tokens present in the enumeration but missing an owner entry.
+        pub missing_owners: Vec<TokenId>,
+        /// Owned tokens missing both an acknowledgement record and a
+        /// consolidated summary.
+        pub missing_acknowledgements: Vec<TokenId>,
+        /// Tokens whose `token_index` entry disagrees with their actual
+        /// position in the enumeration.
+        pub index_mismatches: Vec<TokenId>,
+        /// Owners whose stored token count disagrees with a recount of the
+        /// enumeration.
+        pub owner_count_mismatches: Vec<AccountId>,
+    }
+
+    /// The owner operations sensitive enough to go through the timelock
+    /// queue once a delay is configured (see
+    /// [`FaNft::set_timelock_delay`]).
+    // the codec derive casts each variant's index `usize as u8` and only
+    // allows the truncation lint on unit variants, so data-carrying
+    // enums have to spell the allow themselves
+    #[allow(clippy::cast_possible_truncation)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum AdminAction {
+        /// Replaces the collection's code with the uploaded `code_hash`.
+        /// Upgrades have no direct message at all: they are only
+        /// reachable through the queue.
+        UpgradeCode(Hash),
+        /// Applies [`FaNft::set_transfer_fee`].
+        SetTransferFee(u16),
+        /// Applies [`FaNft::set_treasury`].
+        SetTreasury(AccountId),
+    }
+
+    /// The parameters of [`FaNft::derive_token_id`], exposed by
+    /// [`FaNft::token_id_derivation`] so off-chain indexers can
+    /// re-implement the derivation and detect when a deployment uses a
+    /// different scheme. Known input/output pairs live in the crate's
+    /// `fixtures` module.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TokenIdDerivation {
+        /// ASCII name of the hash applied to the SCALE-encoded
+        /// `(cid, account, block)` tuple.
+        pub hasher: Vec<u8>,
+        /// Number of leading digest bytes forming the id.
+        pub id_bytes: u8,
+        /// Whether those bytes are read as a little-endian integer.
+        pub little_endian: bool,
+    }
+
+    #[ink(storage)]
+    pub struct FaNft {
+        /// Ownership of the collection.
+        ownership: OwnershipData,
+        /// Operational guardian, limited to pausing the collection.
+        guardian: GuardianData,
+        /// When `true`, mints and transfers are suspended.
+        paused: bool,
+        /// The account allowed to mint new tokens, when set.
+        minter: Option<AccountId>,
+        /// Mapping from token to owner.
+        token_owner: Mapping<TokenId, AccountId>,
+        /// Mapping from token to approved account.
+        token_approvals: Mapping<TokenId, AccountId>,
+        /// Number of tokens owned by each account.
+        owned_tokens_count: Mapping<AccountId, u32>,
+        /// Token at each `(owner, index)` slot of the per-owner
+        /// enumeration, maintained swap-and-pop so every mutation is O(1).
+        owned_tokens: Mapping<(AccountId, u32), TokenId>,
+        /// Position of each token in its owner's enumeration.
+        owned_token_index: Mapping<TokenId, u32>,
+        /// Operator approvals granted by owners.
+        operator_approvals: Mapping<(AccountId, AccountId), ()>,
+        /// Tokens each owner currently has a per-token approval on, so
+        /// all of them can be revoked in one transaction.
+        approvals_by_owner: Mapping<AccountId, Vec<TokenId>>,
+        /// How many blocks a freshly minted acknowledgement stays valid,
+        /// if the round requires periodic re-acknowledgement.
+        validity_period: Option<BlockNumber>,
+        /// The block after which each token stops counting as a live
+        /// acknowledgement. Absent for tokens minted while no validity
+        /// period was configured; those never expire.
+        valid_until: Mapping<TokenId, BlockNumber>,
+        /// Short holder-attached notes, e.g. the storage path or replica
+        /// location label of the acknowledged fragment.
+        memos: Mapping<TokenId, Vec<u8>>,
+        /// Operators the contract owner has vetted (e.g. audited
+        /// marketplaces). Only consulted while `restrict_operators` is
+        /// set.
+        operator_allowlist: Mapping<AccountId, ()>,
+        /// When `true`, `set_approval_for_all` only accepts operators on
+        /// the allowlist.
+        restrict_operators: bool,
+        /// Operators each owner currently has enabled.
+        operators_by_owner: Mapping<AccountId, Vec<AccountId>>,
+        /// Acknowledgement data attached to each token at mint time.
+        acknowledgements: Mapping<TokenId, FragmentAcknowledgement>,
+        /// Every live token, in unspecified order, for enumeration.
+        all_tokens: StorageVec<TokenId>,
+        /// Position of each live token in `all_tokens`.
+        token_index: Mapping<TokenId, u32>,
+        /// Number of live acknowledgements per fragment cid.
+        acknowledgment_counts: Mapping<FragmentCid, u32>,
+        /// Active custodian delegations per token.
+        delegations: Mapping<TokenId, Delegation>,
+        /// Time-boxed usage grants per token.
+        users: Mapping<TokenId, UserInfo>,
+        /// Summary records carried by consolidated tokens.
+        summaries: Mapping<TokenId, ConsolidatedSummary>,
+        /// Ring buffer of each token's most recent holders, oldest first.
+        provenance: Mapping<TokenId, Vec<ProvenanceEntry>>,
+        /// Listener contracts notified after every mint, transfer and
+        /// burn. Owner-managed and expected to stay short.
+        hooks: Vec<AccountId>,
+        /// Owner override of the weight budget each hook notification
+        /// runs under; `None` uses the built-in default.
+        hook_budget: Option<CallBudget>,
+        /// Contracts approved to lock tokens as collateral.
+        lockers: Mapping<AccountId, ()>,
+        /// The locker currently holding each token as collateral. Locked
+        /// tokens cannot be transferred or burned.
+        collateral_locks: Mapping<TokenId, AccountId>,
+        /// Treasury cut, in basis points, taken from the value attached
+        /// to payable transfers. Zero disables the fee.
+        transfer_fee_bps: u16,
+        /// Accounts whose transfers are never skimmed: the round,
+        /// staking, or marketplace contracts moving tokens as part of
+        /// the protocol itself.
+        fee_exempt: Mapping<AccountId, ()>,
+        /// Treasury accounting for the fees collected here.
+        treasury: TreasuryData,
+        /// Timelock queue for sensitive owner operations. Disabled until
+        /// the owner configures a delay.
+        timelock: TimelockData<AdminAction>,
+        /// Ring buffer of recent privileged calls, for on-chain review.
+        admin_log: AdminLogData,
+        /// Acknowledgements the minting round has marked as consumed by a
+        /// reward payout. Spent tokens stay owned and transferable but
+        /// other reward schemes can refuse them.
+        spent: Mapping<TokenId, ()>,
+        /// Display metadata per fragment cid, mirrored from the minting
+        /// round so token attributes resolve without a cross-contract
+        /// call.
+        fragment_meta: Mapping<FragmentCid, FragmentMeta>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        NotOwner,
+        NotApproved,
+        TokenExists,
+        TokenNotFound,
+        CannotInsert,
+        CannotFetchValue,
+        NotAllowed,
+        NotMinter,
+        NotLocker,
+        TokenLocked,
+        OperatorNotAllowed,
+        MemoTooLong,
+        NothingToConsolidate,
+        ContractPaused,
+        NotGuardian,
+        InvalidFee,
+        TransferFailed,
+        NothingToWithdraw,
+        TimelockRequired,
+        UnknownAction,
+        ActionNotReady,
+        UpgradeFailed,
+        MetaTooLong,
+    }
+
+    impl Error {
+        /// The variant's stable numeric code, for wallets and dry-run
+        /// tooling to key [`FaNft::error_description`] by. Codes are
+        /// append-only: a new variant takes the next free code and
+        /// existing codes never change or get reused.
+        pub fn code(&self) -> u16 {
+            match self {
+                Error::NotOwner => 1,
+                Error::NotApproved => 2,
+                Error::TokenExists => 3,
+                Error::TokenNotFound => 4,
+                Error::CannotInsert => 5,
+                Error::CannotFetchValue => 6,
+                Error::NotAllowed => 7,
+                Error::NotMinter => 8,
+                Error::NotLocker => 9,
+                Error::TokenLocked => 10,
+                Error::OperatorNotAllowed => 11,
+                Error::MemoTooLong => 12,
+                Error::NothingToConsolidate => 13,
+                Error::ContractPaused => 14,
+                Error::NotGuardian => 15,
+                Error::InvalidFee => 16,
+                Error::TransferFailed => 17,
+                Error::NothingToWithdraw => 18,
+                Error::TimelockRequired => 19,
+                Error::UnknownAction => 20,
+                Error::ActionNotReady => 21,
+                Error::UpgradeFailed => 22,
+                Error::MetaTooLong => 23,
+            }
+        }
+    }
+
+    /// Emitted when a token is transferred, including mints (`from` is
+    /// `None`) and burns (`to` is `None`).
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Emitted when a token's approved account changes.
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Emitted when a token's custodian role is delegated.
+    #[ink(event)]
+    pub struct Delegated {
+        #[ink(topic)]
+        id: TokenId,
+        #[ink(topic)]
+        operator: AccountId,
+        until_block: BlockNumber,
+    }
+
+    /// Emitted when a token's delegation is revoked before expiry.
+    #[ink(event)]
+    pub struct DelegationRevoked {
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Emitted when the minting round marks an acknowledgement as
+    /// consumed by a reward payout.
+    #[ink(event)]
+    pub struct AcknowledgementSpent {
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Emitted when acknowledgements are merged into a summary token.
+    #[ink(event)]
+    pub struct Consolidated {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        id: TokenId,
+        merged: u32,
+    }
+
+    /// Emitted when a token's usage grant changes.
+    #[ink(event)]
+    pub struct UserUpdated {
+        #[ink(topic)]
+        id: TokenId,
+        #[ink(topic)]
+        user: AccountId,
+        expires: BlockNumber,
+    }
+
+    /// Emitted when a token is locked as collateral.
+    #[ink(event)]
+    pub struct CollateralLocked {
+        #[ink(topic)]
+        id: TokenId,
+        #[ink(topic)]
+        locker: AccountId,
+    }
+
+    /// Emitted when a token's collateral lock is released.
+    #[ink(event)]
+    pub struct CollateralReleased {
+        #[ink(topic)]
+        id: TokenId,
+    }
+
+    /// Emitted when an owner enables or disables an operator.
+    #[ink(event)]
+    pub struct ApprovalForAll {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        approved: bool,
+    }
+
+    /// Emitted whenever the minter authority changes — a grant, a
+    /// replacement, or a revocation — so security monitoring can track
+    /// exactly which contract is able to mint acknowledgements at any
+    /// time. A `minter` of `None` means minting is disabled.
+    #[ink(event)]
+    pub struct MinterChanged {
+        previous: Option<AccountId>,
+        #[ink(topic)]
+        minter: Option<AccountId>,
+    }
+
+    /// Emitted when a sensitive owner operation is queued behind the
+    /// timelock, so watchers can react before it becomes executable.
+    #[ink(event)]
+    pub struct AdminActionScheduled {
+        #[ink(topic)]
+        id: ActionId,
+        /// The first block at which the action may be executed.
+        executable_at: BlockNumber,
+    }
+
+    /// Emitted when a scheduled admin action is cancelled.
+    #[ink(event)]
+    pub struct AdminActionCancelled {
+        #[ink(topic)]
+        id: ActionId,
+    }
+
+    /// Emitted when a scheduled admin action is executed.
+    #[ink(event)]
+    pub struct AdminActionExecuted {
+        #[ink(topic)]
+        id: ActionId,
+    }
+
+    /// Emitted when the timelock delay changes, including the initial
+    /// enablement from zero.
+    #[ink(event)]
+    pub struct TimelockDelayUpdated {
+        delay: BlockNumber,
+    }
+
+    /// Emitted when [`FaNft::rebuild_owner_counts`] reconciles a page
+    /// of the enumeration: what the page covered and how many stored
+    /// per-owner counts had drifted and were rewritten.
+    #[ink(event)]
+    pub struct OwnerCountsRebuilt {
+        /// The enumeration offset the page started at.
+        offset: u32,
+        /// Number of enumeration slots inspected.
+        tokens_checked: u32,
+        /// Number of distinct owners recounted.
+        owners_checked: u32,
+        /// Number of stored counts corrected.
+        counts_fixed: u32,
+    }
+
+    impl FaNft {
+        /// Creates a new fragment acknowledgement collection owned by the
+        /// caller, with no minter set.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                ownership: OwnershipData::new(Self::env().caller()),
+                guardian: GuardianData::new(),
+                paused: false,
+                minter: None,
+                token_owner: Mapping::default(),
+                token_approvals: Mapping::default(),
+                owned_tokens_count: Mapping::default(),
+                owned_tokens: Mapping::default(),
+                owned_token_index: Mapping::default(),
+                operator_approvals: Mapping::default(),
+                approvals_by_owner: Mapping::default(),
+                validity_period: None,
+                valid_until: Mapping::default(),
+                memos: Mapping::default(),
+                operator_allowlist: Mapping::default(),
+                restrict_operators: false,
+                operators_by_owner: Mapping::default(),
+                acknowledgements: Mapping::default(),
+                all_tokens: StorageVec::default(),
+                token_index: Mapping::default(),
+                acknowledgment_counts: Mapping::default(),
+                delegations: Mapping::default(),
+                users: Mapping::default(),
+                summaries: Mapping::default(),
+                provenance: Mapping::default(),
+                hooks: Vec::new(),
+                hook_budget: None,
+                lockers: Mapping::default(),
+                collateral_locks: Mapping::default(),
+                transfer_fee_bps: 0,
+                fee_exempt: Mapping::default(),
+                treasury: TreasuryData::new(Self::env().caller()),
+                timelock: TimelockData::new(),
+                admin_log: AdminLogData::new(),
+                spent: Mapping::default(),
+                fragment_meta: Mapping::default(),
+            }
+        }
+
+        /// Number of holders retained in each token's provenance buffer.
+        const PROVENANCE_CAPACITY: usize = 8;
+
+        /// Weight granted to each hook notification, so a misbehaving
+        /// listener cannot exhaust the movement's gas.
+        const HOOK_REF_TIME_LIMIT: u64 = 2_000_000_000;
+        /// Proof-size bound granted to each hook notification.
+        const HOOK_PROOF_SIZE_LIMIT: u64 = 64 * 1024;
+        /// Maximum length of a holder-attached memo, in bytes.
+        const MEMO_CAPACITY: usize = 128;
+        /// Maximum length of a fragment metadata title, in bytes.
+        pub const META_TITLE_CAPACITY: usize = 64;
+        /// Maximum length of a fragment metadata description, in bytes.
+        pub const META_DESCRIPTION_CAPACITY: usize = 256;
+        /// Maximum length of a fragment metadata content-type hint, in
+        /// bytes.
+        pub const META_CONTENT_TYPE_CAPACITY: usize = 64;
+        /// Maximum length of a fragment cid, in bytes. Generous enough
+        /// for any CIDv1 with a 64-byte multihash digest.
+        pub const MAX_CID_LENGTH: usize = 96;
+
+        /// Suspends or resumes mints and transfers. Burns and queries keep
+        /// working, so holders can always exit. While paused, mints are
+        /// refused as `NotAllowed` and transfers as
+        /// [`Error::ContractPaused`].
+        ///
+        /// Callable by the contract owner or the guardian.
+        #[ink(message)]
+        pub fn set_paused(&mut self, paused: bool) -> Result<(), Error> {
+            self.ensure_owner_or_guardian()?;
+            self.log_admin(b"set_paused", paused.encode());
+            self.paused = paused;
+            Ok(())
+        }
+
+        /// Returns `true` while mints and transfers are suspended.
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Configures how many blocks newly minted acknowledgements stay
+        /// valid, or `None` to mint non-expiring tokens. Tokens already
+        /// minted keep whatever window they were born with, so rounds can
+        /// require periodic re-acknowledgement without burning historical
+        /// tokens.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_validity_period(
+            &mut self,
+            period: Option<BlockNumber>,
+        ) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"set_validity_period", period.encode());
+            self.validity_period = period;
+            Ok(())
+        }
+
+        /// Returns the configured validity period, if any.
+        #[ink(message)]
+        pub fn validity_period(&self) -> Option<BlockNumber> {
+            self.validity_period
+        }
+
+        /// Returns the block after which token `id` expires, if it
+        /// carries a validity window.
+        #[ink(message)]
+        pub fn valid_until(&self, id: TokenId) -> Option<BlockNumber> {
+            self.valid_until.get(id)
+        }
+
+        /// Returns `true` if token `id` exists and still counts as a live
+        /// acknowledgement. Tokens without a validity window never expire.
+        #[ink(message)]
+        pub fn is_valid(&self, id: TokenId) -> bool {
+            if !self.token_owner.contains(id) {
+                return false;
+            }
+            match self.valid_until.get(id) {
+                Some(until) => self.env().block_number() <= until,
+                None => true,
+            }
+        }
+
+        /// Attaches a short note to token `id`, e.g. the storage path or
+        /// replica location label of the acknowledged fragment. An empty
+        /// memo clears any existing one.
+        ///
+        /// Only callable by the token owner.
+        #[ink(message)]
+        pub fn set_memo(&mut self, id: TokenId, memo: Vec<u8>) -> Result<(), Error> {
+            let owner = self.token_owner.get(id).ok_or(Error::TokenNotFound)?;
+            if owner != self.env().caller() {
+                return Err(Error::NotAllowed);
+            }
+            if memo.len() > Self::MEMO_CAPACITY {
+                return Err(Error::MemoTooLong);
+            }
+            if memo.is_empty() {
+                self.memos.remove(id);
+            } else {
+                self.memos.insert(id, &memo);
+            }
+            Ok(())
+        }
+
+        /// Returns the memo attached to token `id`, if any.
+        #[ink(message)]
+        pub fn memo(&self, id: TokenId) -> Option<Vec<u8>> {
+            self.memos.get(id)
+        }
+
+        /// Switches operator restriction on or off. While enabled,
+        /// `set_approval_for_all` only accepts operators on the
+        /// owner-curated allowlist, shielding holders from
+        /// approval-phishing. Revocations always go through.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_operator_restriction(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.restrict_operators = enabled;
+            Ok(())
+        }
+
+        /// Returns `true` if operator approvals are restricted to the
+        /// allowlist.
+        #[ink(message)]
+        pub fn operator_restriction(&self) -> bool {
+            self.restrict_operators
+        }
+
+        /// Adds `operator` to the allowlist consulted while operator
+        /// restriction is enabled.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn allow_operator(&mut self, operator: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.operator_allowlist.insert(operator, &());
+            Ok(())
+        }
+
+        /// Removes `operator` from the allowlist. Approvals already
+        /// granted stay in force; holders can drop them individually or
+        /// via `revoke_all_approvals`.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn disallow_operator(&mut self, operator: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.operator_allowlist.remove(operator);
+            Ok(())
+        }
+
+        /// Returns `true` if `operator` is on the allowlist.
+        #[ink(message)]
+        pub fn is_operator_allowed(&self, operator: AccountId) -> bool {
+            self.operator_allowlist.contains(operator)
+        }
+
+        /// Approves `locker` (normally a lending or bond contract) to
+        /// lock tokens as collateral.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn approve_locker(&mut self, locker: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.lockers.insert(locker, &());
+            Ok(())
+        }
+
+        /// Revokes `locker`'s approval. Existing locks stay in force so a
+        /// de-listed locker can still release its collateral.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn revoke_locker(&mut self, locker: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.lockers.remove(locker);
+            Ok(())
+        }
+
+        /// Returns `true` if `account` may lock tokens as collateral.
+        #[ink(message)]
+        pub fn is_locker(&self, account: AccountId) -> bool {
+            self.lockers.contains(account)
+        }
+
+        /// Locks token `id` as collateral held by `locker`, freezing
+        /// transfers and burns until the lock is released. The caller must
+        /// be an approved locker and hold a transfer approval for the
+        /// token, so lockers cannot freeze tokens whose owners never
+        /// engaged with them.
+        #[ink(message)]
+        pub fn lock_as_collateral(&mut self, id: TokenId, locker: AccountId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if !self.lockers.contains(caller) {
+                return Err(Error::NotLocker);
+            }
+            if !self.exists(id) {
+                return Err(Error::TokenNotFound);
+            }
+            if !self.approved_or_owner(caller, id) {
+                return Err(Error::NotApproved);
+            }
+            if self.collateral_locks.contains(id) {
+                return Err(Error::TokenLocked);
+            }
+            self.collateral_locks.insert(id, &locker);
+            self.env().emit_event(CollateralLocked { id, locker });
+            Ok(())
+        }
+
+        /// Releases the collateral lock on token `id`.
+        ///
+        /// Only callable by the locker holding the lock.
+        #[ink(message)]
+        pub fn release_collateral(&mut self, id: TokenId) -> Result<(), Error> {
+            let locker = self.collateral_locks.get(id).ok_or(Error::TokenNotFound)?;
+            if locker != self.env().caller() {
+                return Err(Error::NotAllowed);
+            }
+            self.collateral_locks.remove(id);
+            self.env().emit_event(CollateralReleased { id });
+            Ok(())
+        }
+
+        /// Returns the locker holding token `id` as collateral, if any.
+        #[ink(message)]
+        pub fn collateral_locker_of(&self, id: TokenId) -> Option<AccountId> {
+            self.collateral_locks.get(id)
+        }
+
+        /// Registers `hook` to be notified of token movements. Idempotent.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn add_hook(&mut self, hook: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"add_hook", hook.encode());
+            if !self.hooks.contains(&hook) {
+                self.hooks.push(hook);
+            }
+            Ok(())
+        }
+
+        /// Unregisters `hook` from token-movement notifications.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn remove_hook(&mut self, hook: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.hooks.retain(|registered| *registered != hook);
+            Ok(())
+        }
+
+        /// Returns the registered token-movement hooks.
+        #[ink(message)]
+        pub fn get_hooks(&self) -> Vec<AccountId> {
+            self.hooks.clone()
+        }
+
+        /// Overrides the weight budget each hook notification runs
+        /// under, or restores the built-in default when `None`, so
+        /// operators can make room for a heavier listener without a
+        /// code upgrade. Notifications stay best effort either way.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_hook_budget(&mut self, budget: Option<CallBudget>) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"set_hook_budget", budget.encode());
+            self.hook_budget = budget;
+            Ok(())
+        }
+
+        /// Returns the effective per-hook notification budget: the
+        /// owner's override, or the built-in default.
+        #[ink(message)]
+        pub fn get_hook_budget(&self) -> CallBudget {
+            self.hook_budget.unwrap_or(CallBudget {
+                ref_time: Self::HOOK_REF_TIME_LIMIT,
+                proof_size: Self::HOOK_PROOF_SIZE_LIMIT,
+            })
+        }
+
+        /// Notifies every registered hook of a token movement, after the
+        /// collection's own state is settled so listeners observe the
+        /// final ownership. Best effort with bounded weight per hook: a
+        /// trapping or misconfigured listener must not block the movement.
+        fn notify_hooks(&mut self, from: Option<AccountId>, to: Option<AccountId>, id: TokenId) {
+            let budget = self.get_hook_budget();
+            for hook in self.hooks.clone() {
+                let mut hook: ink::contract_ref!(TransferHook) = hook.into();
+                let _ = hook
+                    .call_mut()
+                    .on_token_transfer(from, to, id)
+                    .ref_time_limit(budget.ref_time)
+                    .proof_size_limit(budget.proof_size)
+                    .try_invoke();
+            }
+        }
+
+        /// Sets the account allowed to mint acknowledgement tokens.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_minter(&mut self, minter: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"set_minter", minter.encode());
+            let previous = self.minter.replace(minter);
+            self.env().emit_event(MinterChanged {
+                previous,
+                minter: Some(minter),
+            });
+            Ok(())
+        }
+
+        /// Revokes the minter authority, disabling minting until a new
+        /// minter is set. Already-minted tokens are unaffected.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn revoke_minter(&mut self) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"revoke_minter", Vec::new());
+            let previous = self.minter.take();
+            self.env().emit_event(MinterChanged { previous, minter: None });
+            Ok(())
+        }
+
+        /// Returns the account currently allowed to mint, if any.
+        #[ink(message)]
+        pub fn get_minter(&self) -> Option<AccountId> {
+            self.minter
+        }
+
+        /// Returns the number of tokens owned by `owner`.
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> u32 {
+            self.owned_tokens_count.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the owner of the token, if it exists.
+        #[ink(message)]
+        pub fn owner_of(&self, id: TokenId) -> Option<AccountId> {
+            self.token_owner.get(id)
+        }
+
+        /// Returns the account approved for the token, if any.
+        #[ink(message)]
+        pub fn get_approved(&self, id: TokenId) -> Option<AccountId> {
+            self.token_approvals.get(id)
+        }
+
+        /// Returns `true` if `operator` is approved to manage all of
+        /// `owner`'s tokens.
+        #[ink(message)]
+        pub fn is_approved_for_all(&self, owner: AccountId, operator: AccountId) -> bool {
+            self.operator_approvals.contains((owner, operator))
+        }
+
+        /// Approves or disapproves `operator` for all of the caller's tokens.
+        #[ink(message)]
+        pub fn set_approval_for_all(
+            &mut self,
+            operator: AccountId,
+            approved: bool,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(Error::NotAllowed);
+            }
+            if approved
+                && self.restrict_operators
+                && !self.operator_allowlist.contains(operator)
+            {
+                return Err(Error::OperatorNotAllowed);
+            }
+            let mut operators = self.operators_by_owner.get(caller).unwrap_or_default();
+            if approved {
+                self.operator_approvals.insert((caller, operator), &());
+                if !operators.contains(&operator) {
+                    operators.push(operator);
+                }
+            } else {
+                self.operator_approvals.remove((caller, operator));
+                operators.retain(|enabled| *enabled != operator);
+            }
+            self.operators_by_owner.insert(caller, &operators);
+            self.env().emit_event(ApprovalForAll {
+                owner: caller,
+                operator,
+                approved,
+            });
+            Ok(())
+        }
+
+        /// Clears every per-token approval and operator approval the
+        /// caller has granted, in one transaction — a panic button for
+        /// responding to a compromised operator or a phished approval.
+        #[ink(message)]
+        pub fn revoke_all_approvals(&mut self) {
+            let caller = self.env().caller();
+            for id in self.approvals_by_owner.take(caller).unwrap_or_default() {
+                self.token_approvals.remove(id);
+            }
+            for operator in self.operators_by_owner.take(caller).unwrap_or_default() {
+                self.operator_approvals.remove((caller, operator));
+                self.env().emit_event(ApprovalForAll {
+                    owner: caller,
+                    operator,
+                    approved: false,
+                });
+            }
+        }
+
+        /// Approves `to` to transfer the token on the owner's behalf.
+        #[ink(message)]
+        pub fn approve(&mut self, to: AccountId, id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let owner = self.token_owner.get(id).ok_or(Error::TokenNotFound)?;
+            if owner != caller && !self.is_approved_for_all(owner, caller) {
+                return Err(Error::NotAllowed);
+            }
+            if self.token_approvals.contains(id) {
+                return Err(Error::CannotInsert);
+            }
+            self.token_approvals.insert(id, &to);
+            let mut approved_ids = self.approvals_by_owner.get(owner).unwrap_or_default();
+            if !approved_ids.contains(&id) {
+                approved_ids.push(id);
+            }
+            self.approvals_by_owner.insert(owner, &approved_ids);
+            self.env().emit_event(Approval {
+                from: caller,
+                to,
+                id,
+            });
+            Ok(())
+        }
+
+        /// Transfers the caller's token `id` to `destination`. Payable:
+        /// any attached value is forwarded to the caller less the
+        /// configured transfer fee (see [`Self::set_transfer_fee`]).
+        #[ink(message, payable)]
+        pub fn transfer(&mut self, destination: AccountId, id: TokenId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            self.transfer_token_from(&caller, &destination, id)?;
+            self.settle_transfer_payment(caller)
+        }
+
+        /// Transfers token `id` from `from` to `to`, if the caller is the
+        /// owner, approved for the token, or an approved operator.
+        /// Payable: marketplaces route the sale price as the attached
+        /// value, which is forwarded to `from` less the configured
+        /// transfer fee.
+        #[ink(message, payable)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            id: TokenId,
+        ) -> Result<(), Error> {
+            self.transfer_token_from(&from, &to, id)?;
+            self.settle_transfer_payment(from)
+        }
+
+        /// Forwards the value attached to a transfer to `seller`, less
+        /// the treasury's cut. Callers on the fee exemption list are
+        /// never skimmed.
+        fn settle_transfer_payment(&mut self, seller: AccountId) -> Result<(), Error> {
+            let value = self.env().transferred_value();
+            if value == 0 {
+                return Ok(());
+            }
+            let fee = if self.fee_exempt.contains(self.env().caller()) {
+                0
+            } else {
+                value.saturating_mul(Balance::from(self.transfer_fee_bps)) / 10_000
+            };
+            if fee > 0 {
+                self.treasury.record_fee(FeeSource::Transfers, fee);
+            }
+            let proceeds = value.saturating_sub(fee);
+            if proceeds > 0 {
+                self.env()
+                    .transfer(seller, proceeds)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+            Ok(())
+        }
+
+        /// Sets the treasury's cut of the value attached to payable
+        /// transfers, in basis points; zero disables the fee. Lets
+        /// deployments route secondary-market proceeds into the reward
+        /// pool.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn set_transfer_fee(&mut self, fee_bps: u16) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.ensure_not_timelocked()?;
+            self.log_admin(b"set_transfer_fee", fee_bps.encode());
+            if fee_bps > 10_000 {
+                return Err(Error::InvalidFee);
+            }
+            self.transfer_fee_bps = fee_bps;
+            Ok(())
+        }
+
+        /// Returns the transfer fee in basis points, zero when disabled.
+        #[ink(message)]
+        pub fn get_transfer_fee(&self) -> u16 {
+            self.transfer_fee_bps
+        }
+
+        /// Adds `account` to or removes it from the fee exemption list.
+        /// Intended for the round, staking, and marketplace contracts
+        /// whose transfers are protocol machinery rather than sales.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn set_fee_exempt(&mut self, account: AccountId, exempt: bool) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"set_fee_exempt", (account, exempt).encode());
+            if exempt {
+                self.fee_exempt.insert(account, &());
+            } else {
+                self.fee_exempt.remove(account);
+            }
+            Ok(())
+        }
+
+        /// Returns whether `account`'s transfers are exempt from the fee.
+        #[ink(message)]
+        pub fn is_fee_exempt(&self, account: AccountId) -> bool {
+            self.fee_exempt.contains(account)
+        }
+
+        /// Points future treasury withdrawals at `treasury`.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: AccountId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.ensure_not_timelocked()?;
+            self.log_admin(b"set_treasury", treasury.encode());
+            self.treasury.set_treasury(treasury);
+            Ok(())
+        }
+
+        /// Returns the fees accrued and not yet withdrawn.
+        #[ink(message)]
+        pub fn treasury_balance(&self) -> Balance {
+            self.treasury.balance()
+        }
+
+        /// Returns the lifetime fee total collected for `source`.
+        #[ink(message)]
+        pub fn fees_of_source(&self, source: FeeSource) -> Balance {
+            self.treasury.of_source(source)
+        }
+
+        /// Drains the accrued fees to the treasury address. Callable by
+        /// anyone, since the destination is fixed.
+        #[ink(message)]
+        pub fn withdraw_treasury(&mut self) -> Result<Balance, Error> {
+            let (recipient, amount) = self.treasury.withdraw();
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.env()
+                .transfer(recipient, amount)
+                .map_err(|_| Error::TransferFailed)?;
+            Ok(amount)
+        }
+
+        /// Sets the number of blocks between scheduling a sensitive owner
+        /// operation and being able to execute it; zero disables the
+        /// timelock. While a delay is configured, the direct messages for
+        /// everything in [`AdminAction`] refuse with
+        /// [`Error::TimelockRequired`] and only the queue applies them.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn set_timelock_delay(&mut self, delay: BlockNumber) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"set_timelock_delay", delay.encode());
+            self.timelock.set_delay(delay);
+            self.env().emit_event(TimelockDelayUpdated { delay });
+            Ok(())
+        }
+
+        /// Returns the configured timelock delay, zero when disabled.
+        #[ink(message)]
+        pub fn get_timelock_delay(&self) -> BlockNumber {
+            self.timelock.delay()
+        }
+
+        /// Queues `action` behind the timelock, returning the id to
+        /// execute or cancel it by.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn schedule_admin_action(&mut self, action: AdminAction) -> Result<ActionId, Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"schedule_admin_action", action.encode());
+            let (id, executable_at) =
+                self.timelock.schedule(action, self.env().block_number());
+            self.env()
+                .emit_event(AdminActionScheduled { id, executable_at });
+            Ok(id)
+        }
+
+        /// Cancels the scheduled admin action `id` before it executes.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn cancel_admin_action(&mut self, id: ActionId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"cancel_admin_action", id.encode());
+            self.timelock.cancel(id).map_err(|_| Error::UnknownAction)?;
+            self.env().emit_event(AdminActionCancelled { id });
+            Ok(())
+        }
+
+        /// Executes the scheduled admin action `id` once its delay has
+        /// elapsed.
+        ///
+        /// Only callable by the collection owner.
+        #[ink(message)]
+        pub fn execute_admin_action(&mut self, id: ActionId) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"execute_admin_action", id.encode());
+            let action = self
+                .timelock
+                .take_ready(id, self.env().block_number())
+                .map_err(|error| match error {
+                    timelock::TimelockError::UnknownAction => Error::UnknownAction,
+                    timelock::TimelockError::NotReady => Error::ActionNotReady,
+                })?;
+            self.apply_admin_action(action)?;
+            self.env().emit_event(AdminActionExecuted { id });
+            Ok(())
+        }
+
+        /// Returns every admin action scheduled and neither executed nor
+        /// cancelled, in scheduling order.
+        #[ink(message)]
+        pub fn pending_admin_actions(&self) -> Vec<Scheduled<AdminAction>> {
+            self.timelock.pending().to_vec()
+        }
+
+        /// Marks token `id` as consumed by a reward payout. Spent tokens
+        /// stay owned and transferable; the flag exists so other reward
+        /// schemes can refuse acknowledgements a round has already paid
+        /// out for. Marking is idempotent.
+        ///
+        /// Only callable by the configured minter — the round that pays
+        /// the rewards.
+        #[ink(message)]
+        pub fn mark_spent(&mut self, id: TokenId) -> Result<(), Error> {
+            if Some(self.env().caller()) != self.minter {
+                return Err(Error::NotMinter);
+            }
+            if !self.token_owner.contains(id) {
+                return Err(Error::TokenNotFound);
+            }
+            if self.spent.contains(id) {
+                return Ok(());
+            }
+            self.spent.insert(id, &());
+            self.env().emit_event(AcknowledgementSpent { id });
+            Ok(())
+        }
+
+        /// Returns `true` if token `id` has been marked as consumed by a
+        /// reward payout.
+        #[ink(message)]
+        pub fn is_spent(&self, id: TokenId) -> bool {
+            self.spent.contains(id)
+        }
+
+        /// Attaches display metadata to a fragment cid, replacing any
+        /// existing record, so every token acknowledging the fragment
+        /// resolves a human-readable title, description, and content
+        /// type.
+        ///
+        /// Only callable by the configured minter — the round that owns
+        /// the fragment manifest.
+        #[ink(message)]
+        pub fn set_fragment_meta(
+            &mut self,
+            cid: FragmentCid,
+            meta: FragmentMeta,
+        ) -> Result<(), Error> {
+            if Some(self.env().caller()) != self.minter {
+                return Err(Error::NotMinter);
+            }
+            if meta.title.len() > Self::META_TITLE_CAPACITY
+                || meta.description.len() > Self::META_DESCRIPTION_CAPACITY
+                || meta.content_type.len() > Self::META_CONTENT_TYPE_CAPACITY
+            {
+                return Err(Error::MetaTooLong);
+            }
+            self.fragment_meta.insert(cid, &meta);
+            Ok(())
+        }
+
+        /// Returns the display metadata attached to `cid`, if any.
+        #[ink(message)]
+        pub fn get_fragment_meta(&self, cid: FragmentCid) -> Option<FragmentMeta> {
+            self.fragment_meta.get(cid)
+        }
+
+        /// Returns the display metadata of the fragment token `id`
+        /// acknowledges, if any — the token's attributes for UIs.
+        #[ink(message)]
+        pub fn token_meta(&self, id: TokenId) -> Option<FragmentMeta> {
+            let acknowledgement = self.acknowledgements.get(id)?;
+            self.fragment_meta.get(acknowledgement.cid)
+        }
+
+        /// Walks the retained log of privileged calls — who called which
+        /// admin message, at which block, with which key arguments —
+        /// oldest entry first, from `offset` for up to `limit` entries.
+        /// The log is a bounded ring buffer (see
+        /// [`admin_log::ADMIN_LOG_CAPACITY`]); follow the events for
+        /// history beyond it.
+        #[ink(message)]
+        pub fn admin_log(&self, offset: u32, limit: u32) -> AdminLogPage {
+            self.admin_log.page(offset, limit)
+        }
+
+        /// A human-readable description of the error behind
+        /// `error_code` (see [`Error::code`]), for wallets to show
+        /// instead of a raw enum index. Meant for dry-runs and off-chain
+        /// lookups.
+        #[ink(message)]
+        pub fn error_description(&self, error_code: u16) -> String {
+            String::from(match error_code {
+                1 => "the caller is not the contract owner",
+                2 => "the caller is not approved to act on this token",
+                3 => "a token with this id already exists",
+                4 => "the token does not exist",
+                5 => "the token could not be recorded",
+                6 => "the token's record could not be fetched",
+                7 => "the caller may not perform this operation",
+                8 => "the caller is not the authorized minter",
+                9 => "the caller is not the authorized collateral locker",
+                10 => "the token is locked as collateral",
+                11 => "the operator's delegation does not cover this operation",
+                12 => "the memo exceeds the maximum length",
+                13 => "the caller has no acknowledgements to consolidate",
+                14 => "the contract is paused",
+                15 => "the caller is neither the owner nor the guardian",
+                16 => "the transfer fee exceeds 100 percent",
+                17 => "the native token transfer failed",
+                18 => "there are no accrued fees to withdraw",
+                19 => "a timelock is enabled, schedule this through the admin action queue",
+                20 => "no scheduled admin action exists under the given id",
+                21 => "the scheduled admin action's delay has not elapsed yet",
+                22 => "the code upgrade failed",
+                23 => "a fragment metadata field exceeds its maximum length",
+                _ => "unknown error code",
+            })
+        }
+
+        /// Identifies the build this address is running: the crate's
+        /// semantic version, the source commit baked in at build time,
+        /// and the non-default features it was compiled with. This crate
+        /// has no optional on-chain features today, so the list is
+        /// empty.
+        #[ink(message)]
+        pub fn contract_info(&self) -> ContractInfo {
+            ContractInfo {
+                version: String::from(env!("CARGO_PKG_VERSION")),
+                commit: String::from(env!("FRAGMENTS_GIT_COMMIT")),
+                features: Vec::new(),
+            }
+        }
+
+        /// Records a privileged call in the admin log. Called after the
+        /// authorization check, so only accepted callers are recorded,
+        /// and rolled back with the rest of the message on error.
+        fn log_admin(&mut self, message: &[u8], args: Vec<u8>) {
+            self.admin_log.record(
+                self.env().caller(),
+                message,
+                self.env().block_number(),
+                args,
+            );
+        }
+
+        /// Applies an admin action whose delay has elapsed. Goes through
+        /// the storage directly rather than the public setters, which
+        /// refuse while the timelock is enabled.
+        fn apply_admin_action(&mut self, action: AdminAction) -> Result<(), Error> {
+            match action {
+                AdminAction::UpgradeCode(code_hash) => self
+                    .env()
+                    .set_code_hash(&code_hash)
+                    .map_err(|_| Error::UpgradeFailed),
+                AdminAction::SetTransferFee(fee_bps) => {
+                    if fee_bps > 10_000 {
+                        return Err(Error::InvalidFee);
+                    }
+                    self.transfer_fee_bps = fee_bps;
+                    Ok(())
+                }
+                AdminAction::SetTreasury(treasury) => {
+                    self.treasury.set_treasury(treasury);
+                    Ok(())
+                }
+            }
+        }
+
+        /// Refuses a direct admin message while the timelock is enabled.
+        fn ensure_not_timelocked(&self) -> Result<(), Error> {
+            if self.timelock.required() {
+                return Err(Error::TimelockRequired);
+            }
+            Ok(())
+        }
+
+        /// Delegates the custodian role for token `id` to `operator` until
+        /// `until_block` (inclusive). Does not grant any transfer rights;
+        /// delegations are cleared when the token moves or is burned.
+        ///
+        /// Only callable by the token's owner.
+        #[ink(message)]
+        pub fn delegate(
+            &mut self,
+            id: TokenId,
+            operator: AccountId,
+            until_block: BlockNumber,
+        ) -> Result<(), Error> {
+            let owner = self.token_owner.get(id).ok_or(Error::TokenNotFound)?;
+            if owner != self.env().caller() {
+                return Err(Error::NotAllowed);
+            }
+            self.delegations.insert(
+                id,
+                &Delegation {
+                    operator,
+                    until_block,
+                },
+            );
+            self.env().emit_event(Delegated {
+                id,
+                operator,
+                until_block,
+            });
+            Ok(())
+        }
+
+        /// Revokes any delegation on token `id`.
+        ///
+        /// Only callable by the token's owner.
+        #[ink(message)]
+        pub fn revoke_delegation(&mut self, id: TokenId) -> Result<(), Error> {
+            let owner = self.token_owner.get(id).ok_or(Error::TokenNotFound)?;
+            if owner != self.env().caller() {
+                return Err(Error::NotAllowed);
+            }
+            if self.delegations.take(id).is_some() {
+                self.env().emit_event(DelegationRevoked { id });
+            }
+            Ok(())
+        }
+
+        /// Returns the active delegation on token `id`, if any. Expired
+        /// delegations are reported as `None`.
+        #[ink(message)]
+        pub fn delegation_of(&self, id: TokenId) -> Option<Delegation> {
+            self.delegations
+                .get(id)
+                .filter(|delegation| delegation.until_block >= self.env().block_number())
+        }
+
+        /// Burns the given tokens — plain acknowledgements or earlier
+        /// summaries — owned by the caller and mints a single summary
+        /// token recording the combined cid set and the earliest claim
+        /// block. The per-fragment acknowledgement counts are preserved:
+        /// the summary keeps its fragments acknowledged until it is
+        /// burned itself. Intended to cut storage and wallet clutter for
+        /// prolific storage nodes.
+        #[ink(message)]
+        pub fn consolidate(&mut self, ids: Vec<TokenId>) -> Result<TokenId, Error> {
+            if ids.len() < 2 {
+                return Err(Error::NothingToConsolidate);
+            }
+            let caller = self.env().caller();
+            // validate the whole batch before burning anything, so a
+            // refused id cannot leave the earlier tokens already burned
+            for (index, id) in ids.iter().enumerate() {
+                if self.token_owner.get(id).ok_or(Error::TokenNotFound)? != caller {
+                    return Err(Error::NotAllowed);
+                }
+                if self.collateral_locks.contains(id) {
+                    return Err(Error::TokenLocked);
+                }
+                // a repeated id would already be burned by its first
+                // mention
+                if ids[..index].contains(id) {
+                    return Err(Error::NotAllowed);
+                }
+            }
+            let mut cids: Vec<FragmentCid> = Vec::new();
+            let mut burned_cids: Vec<FragmentCid> = Vec::new();
+            let mut earliest_block = BlockNumber::MAX;
+            let mut merged = 0u32;
+            for id in &ids {
+                if let Some(ack) = self.acknowledgements.get(id) {
+                    if !cids.contains(&ack.cid) {
+                        cids.push(ack.cid.clone());
+                    }
+                    burned_cids.push(ack.cid);
+                    earliest_block = earliest_block.min(ack.block);
+                    merged = merged.saturating_add(1);
+                } else if let Some(summary) = self.summaries.get(id) {
+                    for cid in &summary.cids {
+                        if !cids.contains(cid) {
+                            cids.push(cid.clone());
+                        }
+                        burned_cids.push(cid.clone());
+                    }
+                    earliest_block = earliest_block.min(summary.earliest_block);
+                    merged = merged.saturating_add(summary.merged);
+                }
+                self.burn_token(caller, *id).map_err(|error| match error {
+                    BurnError::TokenNotFound => Error::TokenNotFound,
+                    BurnError::NotAllowed => Error::TokenLocked,
+                })?;
+            }
+            let block = self.env().block_number();
+            let mut digest = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Keccak256, _>(
+                &(&cids, caller, block),
+                &mut digest,
+            );
+            let id = u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]);
+            self.add_token_to(&caller, id)?;
+            self.token_index.insert(id, &self.all_tokens.len());
+            self.all_tokens.push(&id);
+            // burning decremented the per-fragment counts; the summary
+            // keeps those acknowledgements live, so restore them
+            for cid in burned_cids {
+                let count = self.acknowledgment_counts.get(&cid).unwrap_or(0);
+                self.acknowledgment_counts.insert(&cid, &count.saturating_add(1));
+            }
+            self.summaries.insert(
+                id,
+                &ConsolidatedSummary {
+                    cids,
+                    earliest_block,
+                    merged,
+                },
+            );
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                id,
+            });
+            self.env().emit_event(Consolidated {
+                owner: caller,
+                id,
+                merged,
+            });
+            self.notify_hooks(None, Some(caller), id);
+            Ok(id)
+        }
+
+        /// Returns the summary record carried by token `id`, if it is a
+        /// consolidated token.
+        #[ink(message)]
+        pub fn get_summary(&self, id: TokenId) -> Option<ConsolidatedSummary> {
+            self.summaries.get(id)
+        }
+
+        /// Returns the most recent holders of token `id`, oldest first,
+        /// capped at the provenance buffer's capacity, so disputes about
+        /// who held an acknowledgement when need no event archaeology.
+        #[ink(message)]
+        pub fn provenance(&self, id: TokenId) -> Vec<ProvenanceEntry> {
+            self.provenance.get(id).unwrap_or_default()
+        }
+
+        /// Grants `user` time-boxed usage rights over token `id` until
+        /// `expires` (inclusive), ERC-4907 style. Grants carry no transfer
+        /// rights, are replaced by subsequent calls, and are cleared when
+        /// the token moves.
+        ///
+        /// Callable by the token's owner, its approved account, or an
+        /// approved operator.
+        #[ink(message)]
+        pub fn set_user(
+            &mut self,
+            id: TokenId,
+            user: AccountId,
+            expires: BlockNumber,
+        ) -> Result<(), Error> {
+            if !self.exists(id) {
+                return Err(Error::TokenNotFound);
+            }
+            if !self.approved_or_owner(self.env().caller(), id) {
+                return Err(Error::NotApproved);
+            }
+            self.users.insert(id, &UserInfo { user, expires });
+            self.env().emit_event(UserUpdated { id, user, expires });
+            Ok(())
+        }
+
+        /// Returns the account holding active usage rights over token
+        /// `id`. Expired grants are reported as `None`.
+        #[ink(message)]
+        pub fn user_of(&self, id: TokenId) -> Option<AccountId> {
+            self.users
+                .get(id)
+                .filter(|info| info.expires >= self.env().block_number())
+                .map(|info| info.user)
+        }
+
+        /// Returns the block at which token `id`'s usage grant expires,
+        /// if one is set (possibly already in the past).
+        #[ink(message)]
+        pub fn user_expires(&self, id: TokenId) -> Option<BlockNumber> {
+            self.users.get(id).map(|info| info.expires)
+        }
+
+        /// Walks the token enumeration from `offset` for up to `limit`
+        /// entries, cross-checking the owner mapping, the acknowledgement
+        /// records, the enumeration index, and the per-owner token counts.
+        /// Intended as a dry-run diagnostic after upgrades or migrations;
+        /// page through with the returned `next_offset` on large
+        /// collections.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn check_invariants(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Result<InvariantReport, Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            let supply = self.all_tokens.len();
+            let end = offset.saturating_add(limit).min(supply);
+            let mut report = InvariantReport {
+                tokens_checked: end.saturating_sub(offset),
+                next_offset: (end < supply).then_some(end),
+                missing_owners: Vec::new(),
+                missing_acknowledgements: Vec::new(),
+                index_mismatches: Vec::new(),
+                owner_count_mismatches: Vec::new(),
+            };
+            let mut owners: Vec<AccountId> = Vec::new();
+            for position in offset..end {
+                let Some(id) = self.all_tokens.get(position) else {
+                    continue;
+                };
+                if self.token_index.get(id) != Some(position) {
+                    report.index_mismatches.push(id);
+                }
+                match self.token_owner.get(id) {
+                    Some(owner) => {
+                        if !self.acknowledgements.contains(id) && !self.summaries.contains(id) {
+                            report.missing_acknowledgements.push(id);
+                        }
+                        let owner_slot = self
+                            .owned_token_index
+                            .get(id)
+                            .and_then(|index| self.owned_tokens.get((owner, index)));
+                        if owner_slot != Some(id) && !report.index_mismatches.contains(&id) {
+                            report.index_mismatches.push(id);
+                        }
+                        if !owners.contains(&owner) {
+                            owners.push(owner);
+                        }
+                    }
+                    None => report.missing_owners.push(id),
+                }
+            }
+            for owner in owners {
+                let mut recount = 0u32;
+                for position in 0..supply {
+                    if let Some(id) = self.all_tokens.get(position) {
+                        if self.token_owner.get(id) == Some(owner) {
+                            recount = recount.saturating_add(1);
+                        }
+                    }
+                }
+                if recount != self.balance_of(owner) {
+                    report.owner_count_mismatches.push(owner);
+                }
+            }
+            Ok(report)
+        }
+
+        /// Recomputes the stored token count of every owner holding a
+        /// token in the enumeration page starting at `offset`, walking
+        /// up to `limit` entries, and writes back any count that
+        /// drifted — the repair companion to [`Self::check_invariants`],
+        /// for collections damaged by historical bugs or partial
+        /// migrations. Each owner found in the page is recounted over
+        /// the full enumeration, so a rewritten count is exact even
+        /// when the owner's tokens span pages. Emits
+        /// [`OwnerCountsRebuilt`] as the reconciliation record; page
+        /// through with the returned offset until it is `None`.
+        ///
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn rebuild_owner_counts(
+            &mut self,
+            offset: u32,
+            limit: u32,
+        ) -> Result<Option<u32>, Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            self.log_admin(b"rebuild_owner_counts", (offset, limit).encode());
+            let supply = self.all_tokens.len();
+            let end = offset.saturating_add(limit).min(supply);
+            let mut owners: Vec<AccountId> = Vec::new();
+            for position in offset..end {
+                let Some(id) = self.all_tokens.get(position) else {
+                    continue;
+                };
+                if let Some(owner) = self.token_owner.get(id) {
+                    if !owners.contains(&owner) {
+                        owners.push(owner);
+                    }
+                }
+            }
+            let mut counts_fixed = 0u32;
+            for owner in &owners {
+                let mut recount = 0u32;
+                for position in 0..supply {
+                    if let Some(id) = self.all_tokens.get(position) {
+                        if self.token_owner.get(id) == Some(*owner) {
+                            recount = recount.saturating_add(1);
+                        }
+                    }
+                }
+                if self.owned_tokens_count.get(owner).unwrap_or(0) != recount {
+                    self.owned_tokens_count.insert(owner, &recount);
+                    counts_fixed = counts_fixed.saturating_add(1);
+                }
+            }
+            self.env().emit_event(OwnerCountsRebuilt {
+                offset,
+                tokens_checked: end.saturating_sub(offset),
+                owners_checked: u32::try_from(owners.len()).unwrap_or(u32::MAX),
+                counts_fixed,
+            });
+            Ok((end < supply).then_some(end))
+        }
+
+        /// Describes how [`Self::derive_token_id`] turns mint inputs into
+        /// a token id, so indexers can derive ids without reading them
+        /// back from events.
+        #[ink(message)]
+        pub fn token_id_derivation(&self) -> TokenIdDerivation {
+            TokenIdDerivation {
+                hasher: b"keccak-256".to_vec(),
+                id_bytes: 4,
+                little_endian: true,
+            }
+        }
+
+        /// Derives the token id minted for `(cid, account, block)`.
+        pub fn derive_token_id(
+            cid: &FragmentCid,
+            account: AccountId,
+            block: BlockNumber,
+        ) -> TokenId {
+            let mut digest = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Keccak256, _>(
+                &(cid, account, block),
+                &mut digest,
+            );
+            u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+        }
+
+        /// Admits the contract owner or the appointed guardian, for the
+        /// operational messages the guardian exists for.
+        fn ensure_owner_or_guardian(&self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.ownership.ensure_owner(caller).is_ok() {
+                return Ok(());
+            }
+            self.guardian
+                .ensure_guardian(caller)
+                .map_err(|_| Error::NotGuardian)
+        }
+
+        fn transfer_token_from(
+            &mut self,
+            from: &AccountId,
+            to: &AccountId,
+            id: TokenId,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+            if !self.exists(id) {
+                return Err(Error::TokenNotFound);
+            }
+            if !self.approved_or_owner(caller, id) {
+                return Err(Error::NotApproved);
+            }
+            // `from` must be the actual owner: an approved caller naming
+            // a third party would otherwise corrupt both parties'
+            // enumeration and balance bookkeeping
+            if self.token_owner.get(id) != Some(*from) {
+                return Err(Error::NotOwner);
+            }
+            if self.collateral_locks.contains(id) {
+                return Err(Error::TokenLocked);
+            }
+            self.clear_approval(id);
+            self.delegations.remove(id);
+            self.users.remove(id);
+            self.memos.remove(id);
+            self.remove_token_from(from, id)?;
+            self.add_token_to(to, id)?;
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                id,
+            });
+            self.notify_hooks(Some(*from), Some(*to), id);
+            Ok(())
+        }
+
+        fn add_token_to(&mut self, to: &AccountId, id: TokenId) -> Result<(), Error> {
+            if self.token_owner.contains(id) {
+                return Err(Error::TokenExists);
+            }
+            if *to == AccountId::from([0x0; 32]) {
+                return Err(Error::NotAllowed);
+            }
+            let count = self.owned_tokens_count.get(to).unwrap_or(0).checked_add(1).ok_or(Error::CannotInsert)?;
+            self.owned_tokens_count.insert(to, &count);
+            let index = count.saturating_sub(1);
+            self.owned_tokens.insert((*to, index), &id);
+            self.owned_token_index.insert(id, &index);
+            self.token_owner.insert(id, to);
+            self.record_provenance(id, *to);
+            Ok(())
+        }
+
+        /// Appends `holder` to the token's provenance ring buffer,
+        /// dropping the oldest entry once the capacity is reached.
+        fn record_provenance(&mut self, id: TokenId, holder: AccountId) {
+            let mut entries = self.provenance.get(id).unwrap_or_default();
+            entries.push(ProvenanceEntry {
+                holder,
+                since: self.env().block_number(),
+            });
+            if entries.len() > Self::PROVENANCE_CAPACITY {
+                entries.remove(0);
+            }
+            self.provenance.insert(id, &entries);
+        }
+
+        fn remove_token_from(&mut self, from: &AccountId, id: TokenId) -> Result<(), Error> {
+            if !self.token_owner.contains(id) {
+                return Err(Error::TokenNotFound);
+            }
+            let count = self
+                .owned_tokens_count
+                .get(from)
+                .map(|c| c.saturating_sub(1))
+                .ok_or(Error::CannotFetchValue)?;
+            // swap-and-pop the departing token out of the owner's
+            // enumeration; `count` is already the post-removal length
+            if let Some(index) = self.owned_token_index.get(id) {
+                if index != count {
+                    if let Some(last_id) = self.owned_tokens.get((*from, count)) {
+                        self.owned_tokens.insert((*from, index), &last_id);
+                        self.owned_token_index.insert(last_id, &index);
+                    }
+                }
+                self.owned_tokens.remove((*from, count));
+                self.owned_token_index.remove(id);
+            }
+            self.owned_tokens_count.insert(from, &count);
+            self.token_owner.remove(id);
+            Ok(())
+        }
+
+        /// Removes a token from its owner, the enumeration, and the
+        /// acknowledgement records, emitting a burn `Transfer`.
+        fn burn_token(&mut self, owner: AccountId, id: TokenId) -> Result<(), BurnError> {
+            if self.collateral_locks.contains(id) {
+                return Err(BurnError::NotAllowed);
+            }
+            self.clear_approval(id);
+            self.delegations.remove(id);
+            self.users.remove(id);
+            self.memos.remove(id);
+            self.valid_until.remove(id);
+            self.provenance.remove(id);
+            self.remove_token_from(&owner, id)
+                .map_err(|_| BurnError::TokenNotFound)?;
+            self.remove_from_enumeration(id);
+            if let Some(ack) = self.acknowledgements.take(id) {
+                let count = self.acknowledgment_counts.get(&ack.cid).unwrap_or(0);
+                self.acknowledgment_counts
+                    .insert(&ack.cid, &count.saturating_sub(1));
+            }
+            if let Some(summary) = self.summaries.take(id) {
+                for cid in summary.cids {
+                    let count = self.acknowledgment_counts.get(&cid).unwrap_or(0);
+                    self.acknowledgment_counts
+                        .insert(&cid, &count.saturating_sub(1));
+                }
+            }
+            self.env().emit_event(Transfer {
+                from: Some(owner),
+                to: None,
+                id,
+            });
+            self.notify_hooks(Some(owner), None, id);
+            Ok(())
+        }
+
+        /// Swap-and-pop removal from the global token enumeration.
+        fn remove_from_enumeration(&mut self, id: TokenId) {
+            let Some(index) = self.token_index.get(id) else {
+                return;
+            };
+            let last_index = self.all_tokens.len().saturating_sub(1);
+            if index != last_index {
+                if let Some(last_id) = self.all_tokens.get(last_index) {
+                    self.all_tokens.set(index, &last_id);
+                    self.token_index.insert(last_id, &index);
+                }
+            }
+            self.all_tokens.pop();
+            self.token_index.remove(id);
+        }
+
+        fn clear_approval(&mut self, id: TokenId) {
+            if self.token_approvals.take(id).is_some() {
+                if let Some(owner) = self.token_owner.get(id) {
+                    let mut approved_ids =
+                        self.approvals_by_owner.get(owner).unwrap_or_default();
+                    approved_ids.retain(|approved| *approved != id);
+                    self.approvals_by_owner.insert(owner, &approved_ids);
+                }
+            }
+        }
+
+        fn exists(&self, id: TokenId) -> bool {
+            self.token_owner.contains(id)
+        }
+
+        fn approved_or_owner(&self, account: AccountId, id: TokenId) -> bool {
+            let owner = self.token_owner.get(id);
+            owner == Some(account)
+                || self.token_approvals.get(id) == Some(account)
+                || owner
+                    .map(|owner| self.is_approved_for_all(owner, account))
+                    .unwrap_or(false)
+        }
+
+        /// The shared mint path behind [`Mintable::mint`] and
+        /// [`Mintable::mint_anchored`].
+        fn mint_token(
+            &mut self,
+            to: AccountId,
+            cid: FragmentCid,
+            tier: u8,
+            beacon_round: Option<u64>,
+        ) -> Result<TokenId, MintError> {
+            if Some(self.env().caller()) != self.minter {
+                return Err(MintError::NotMinter);
+            }
+            if self.paused {
+                return Err(MintError::NotAllowed);
+            }
+            if cid.is_empty() || cid.len() > Self::MAX_CID_LENGTH {
+                return Err(MintError::InvalidCid);
+            }
+            let block = self.env().block_number();
+            let id = Self::derive_token_id(&cid, to, block);
+            self.add_token_to(&to, id).map_err(MintError::from)?;
+            self.token_index.insert(id, &self.all_tokens.len());
+            self.all_tokens.push(&id);
+            if let Some(period) = self.validity_period {
+                self.valid_until.insert(id, &block.saturating_add(period));
+            }
+            let count = self.acknowledgment_counts.get(&cid).unwrap_or(0);
+            self.acknowledgment_counts.insert(&cid, &count.saturating_add(1));
+            self.acknowledgements.insert(
+                id,
+                &FragmentAcknowledgement {
+                    cid,
+                    claimer: to,
+                    block,
+                    tier,
+                    beacon_round,
+                },
+            );
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                id,
+            });
+            self.notify_hooks(None, Some(to), id);
+            Ok(id)
+        }
+    }
+
+    impl Default for FaNft {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Mintable for FaNft {
+        /// Mints an acknowledgement token for fragment `cid` to `to`,
+        /// recording the fragment's rarity `tier` in the token's attributes.
+        ///
+        /// Only callable by the configured minter. The token id is derived
+        /// from the fragment, recipient, and current block, so the same
+        /// account acknowledging the same fragment at a later block yields a
+        /// distinct token.
+        #[ink(message)]
+        fn mint(&mut self, to: AccountId, cid: FragmentCid, tier: u8) -> Result<TokenId, MintError> {
+            self.mint_token(to, cid, tier, None)
+        }
+
+        /// Mints like [`Mintable::mint`], additionally recording the beacon
+        /// round of a pulse the minting round verified, as the
+        /// acknowledgement's wall-clock anchor.
+        #[ink(message)]
+        fn mint_anchored(
+            &mut self,
+            to: AccountId,
+            cid: FragmentCid,
+            tier: u8,
+            beacon_round: Option<u64>,
+        ) -> Result<TokenId, MintError> {
+            self.mint_token(to, cid, tier, beacon_round)
+        }
+    }
+
+    impl Acknowledgeable for FaNft {
+        /// Returns the acknowledgement record minted with the token, if the
+        /// token exists.
+        #[ink(message)]
+        fn get_fragment_acknowledgment(&self, id: TokenId) -> Option<FragmentAcknowledgement> {
+            self.acknowledgements.get(id)
+        }
+
+        /// Returns the number of live acknowledgements of fragment `cid`.
+        #[ink(message)]
+        fn acknowledgment_count(&self, cid: FragmentCid) -> u32 {
+            self.acknowledgment_counts.get(cid).unwrap_or(0)
+        }
+
+        /// Returns `true` if fragment `cid` has at least one live
+        /// acknowledgement.
+        #[ink(message)]
+        fn is_acknowledged(&self, cid: FragmentCid) -> bool {
+            self.acknowledgment_count(cid) > 0
+        }
+    }
+
+    impl Burnable for FaNft {
+        /// Burns token `id` owned by the caller.
+        #[ink(message)]
+        fn burn(&mut self, id: TokenId) -> Result<(), BurnError> {
+            let caller = self.env().caller();
+            let owner = self.token_owner.get(id).ok_or(BurnError::TokenNotFound)?;
+            if owner != caller {
+                return Err(BurnError::NotAllowed);
+            }
+            self.burn_token(owner, id)
+        }
+
+        /// Burns token `id` held by `from`, if the caller is the owner,
+        /// approved for the token, or an approved operator.
+        #[ink(message)]
+        fn burn_from(&mut self, from: AccountId, id: TokenId) -> Result<(), BurnError> {
+            let caller = self.env().caller();
+            let owner = self.token_owner.get(id).ok_or(BurnError::TokenNotFound)?;
+            if owner != from || !self.approved_or_owner(caller, id) {
+                return Err(BurnError::NotAllowed);
+            }
+            self.burn_token(owner, id)
+        }
+    }
+
+    impl Enumerable for FaNft {
+        /// Returns the number of live tokens.
+        #[ink(message)]
+        fn total_supply(&self) -> u32 {
+            self.all_tokens.len()
+        }
+
+        /// Returns the token at `index` in the global enumeration, if any.
+        #[ink(message)]
+        fn token_by_index(&self, index: u32) -> Option<TokenId> {
+            self.all_tokens.get(index)
+        }
+
+        /// Returns the token at `index` among those owned by `owner`, if
+        /// any. A direct lookup into the per-owner enumeration, so the
+        /// cost is independent of the collection size.
+        #[ink(message)]
+        fn token_of_owner_by_index(&self, owner: AccountId, index: u32) -> Option<TokenId> {
+            self.owned_tokens.get((owner, index))
+        }
+    }
+
+    impl From<Error> for MintError {
+        fn from(error: Error) -> Self {
+            match error {
+                Error::TokenExists => MintError::TokenExists,
+                Error::NotAllowed => MintError::NotAllowed,
+                Error::NotMinter => MintError::NotMinter,
+                _ => MintError::CannotInsert,
+            }
+        }
+    }
+
+    impl Guarded for FaNft {
+        #[ink(message)]
+        fn guardian(&self) -> Option<AccountId> {
+            self.guardian.guardian()
+        }
+
+        #[ink(message)]
+        fn set_guardian(&mut self, guardian: Option<AccountId>) -> Result<(), GuardianError> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| GuardianError::NotOwner)?;
+            let event = self.guardian.set(guardian);
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    impl Ownable for FaNft {
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            self.ownership.owner()
+        }
+
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+            let event = self.ownership.transfer(self.env().caller(), new_owner)?;
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    impl Ownable2Step for FaNft {
+        #[ink(message)]
+        fn propose_owner(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+            self.ownership.propose(self.env().caller(), new_owner)
+        }
+
+        #[ink(message)]
+        fn pending_owner(&self) -> Option<AccountId> {
+            self.ownership.pending_owner()
+        }
+
+        #[ink(message)]
+        fn accept_ownership(&mut self) -> Result<(), OwnableError> {
+            let event = self.ownership.accept(self.env().caller())?;
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        fn cid(n: u8) -> FragmentCid {
+            // raw-codec CIDv1-style bytes, distinct per fragment
+            vec![0x01, 0x55, n]
+        }
+
+        fn minting_contract() -> FaNft {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut contract = FaNft::new();
+            contract.set_minter(accounts.alice).expect("alice owns the contract");
+            contract
+        }
+
+        #[ink::test]
+        fn mint_records_acknowledgement() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            assert_eq!(contract.owner_of(id), Some(accounts.bob));
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+            let ack = contract.get_fragment_acknowledgment(id).expect("ack exists");
+            assert_eq!(ack.cid, cid(1));
+            assert_eq!(ack.claimer, accounts.bob);
+        }
+
+        #[ink::test]
+        fn mint_anchored_records_the_beacon_round() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let anchored = contract
+                .mint_anchored(accounts.bob, cid(1), 0, Some(42))
+                .expect("anchored mint works");
+            let ack = contract.get_fragment_acknowledgment(anchored).expect("ack exists");
+            assert_eq!(ack.beacon_round, Some(42));
+            let plain = contract.mint(accounts.bob, cid(2), 0).expect("mint works");
+            let ack = contract.get_fragment_acknowledgment(plain).expect("ack exists");
+            assert_eq!(ack.beacon_round, None);
+        }
+
+        #[ink::test]
+        fn mark_spent_is_minter_only_and_idempotent() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            assert!(!contract.is_spent(id));
+            set_caller(accounts.bob);
+            assert_eq!(contract.mark_spent(id), Err(Error::NotMinter));
+            set_caller(accounts.alice);
+            assert_eq!(contract.mark_spent(9999), Err(Error::TokenNotFound));
+            assert_eq!(contract.mark_spent(id), Ok(()));
+            assert!(contract.is_spent(id));
+            assert_eq!(contract.mark_spent(id), Ok(()));
+        }
+
+        #[ink::test]
+        fn hook_budget_is_owner_configured_with_a_default() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let default = contract.get_hook_budget();
+            assert_eq!(default.ref_time, FaNft::HOOK_REF_TIME_LIMIT);
+            let roomy = CallBudget {
+                ref_time: 8_000_000_000,
+                proof_size: 256 * 1024,
+            };
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_hook_budget(Some(roomy)),
+                Err(Error::NotOwner)
+            );
+            set_caller(accounts.alice);
+            assert_eq!(contract.set_hook_budget(Some(roomy)), Ok(()));
+            assert_eq!(contract.get_hook_budget(), roomy);
+            assert_eq!(contract.set_hook_budget(None), Ok(()));
+            assert_eq!(contract.get_hook_budget(), default);
+        }
+
+        #[ink::test]
+        fn fragment_meta_resolves_per_token() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            let meta = FragmentMeta {
+                title: b"shard 1".to_vec(),
+                description: b"first erasure shard of the archive".to_vec(),
+                content_type: b"application/octet-stream".to_vec(),
+            };
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_fragment_meta(cid(1), meta.clone()),
+                Err(Error::NotMinter)
+            );
+            set_caller(accounts.alice);
+            assert_eq!(contract.set_fragment_meta(cid(1), meta.clone()), Ok(()));
+            assert_eq!(contract.get_fragment_meta(cid(1)), Some(meta.clone()));
+            assert_eq!(contract.token_meta(id), Some(meta.clone()));
+            assert_eq!(contract.token_meta(9999), None);
+            let oversized = FragmentMeta {
+                title: vec![0u8; FaNft::META_TITLE_CAPACITY + 1],
+                ..meta
+            };
+            assert_eq!(
+                contract.set_fragment_meta(cid(1), oversized),
+                Err(Error::MetaTooLong)
+            );
+        }
+
+        #[ink::test]
+        fn mint_requires_minter() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut contract = FaNft::new();
+            assert_eq!(contract.mint(accounts.bob, cid(1), 0), Err(MintError::NotMinter));
+            contract.set_minter(accounts.alice).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.mint(accounts.bob, cid(1), 0), Err(MintError::NotMinter));
+        }
+
+        #[ink::test]
+        fn mint_rejects_out_of_bounds_cids() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            assert_eq!(
+                contract.mint(accounts.bob, Vec::new(), 0),
+                Err(MintError::InvalidCid)
+            );
+            assert_eq!(
+                contract.mint(accounts.bob, vec![0u8; FaNft::MAX_CID_LENGTH + 1], 0),
+                Err(MintError::InvalidCid)
+            );
+            assert!(contract
+                .mint(accounts.bob, vec![0u8; FaNft::MAX_CID_LENGTH], 0)
+                .is_ok());
+        }
+
+        #[ink::test]
+        fn derive_token_id_reproduces_the_published_fixtures() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            for vector in crate::fixtures::TOKEN_ID_VECTORS {
+                assert_eq!(
+                    FaNft::derive_token_id(
+                        &vector.cid.to_vec(),
+                        AccountId::from(vector.account),
+                        vector.block,
+                    ),
+                    vector.token_id
+                );
+            }
+            let derivation = FaNft::new().token_id_derivation();
+            assert_eq!(derivation.hasher, b"keccak-256".to_vec());
+            assert_eq!(derivation.id_bytes, 4);
+            assert!(derivation.little_endian);
+        }
+
+        #[ink::test]
+        fn guardian_pauses_mints_and_transfers() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+
+            // only the owner appoints the guardian
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_guardian(Some(accounts.frank)),
+                Err(GuardianError::NotOwner)
+            );
+            assert_eq!(contract.set_paused(true), Err(Error::NotGuardian));
+            set_caller(accounts.alice);
+            assert!(contract.set_guardian(Some(accounts.frank)).is_ok());
+
+            set_caller(accounts.frank);
+            assert!(contract.set_paused(true).is_ok());
+            assert!(contract.is_paused());
+            set_caller(accounts.alice);
+            assert_eq!(
+                contract.mint(accounts.bob, cid(2), 0),
+                Err(MintError::NotAllowed)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer(accounts.charlie, id),
+                Err(Error::ContractPaused)
+            );
+            // burns still work, so holders can always exit
+            assert!(contract.burn(id).is_ok());
+
+            set_caller(accounts.frank);
+            assert!(contract.set_paused(false).is_ok());
+            set_caller(accounts.alice);
+            assert!(contract.mint(accounts.bob, cid(2), 0).is_ok());
+        }
+
+        #[ink::test]
+        fn set_minter_requires_owner() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut contract = FaNft::new();
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_minter(accounts.bob), Err(Error::NotOwner));
+            assert_eq!(contract.revoke_minter(), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn revoking_the_minter_disables_minting() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            assert_eq!(contract.get_minter(), Some(accounts.alice));
+            contract.revoke_minter().expect("alice owns the contract");
+            assert_eq!(contract.get_minter(), None);
+            assert_eq!(contract.mint(accounts.bob, cid(1), 0), Err(MintError::NotMinter));
+        }
+
+        #[ink::test]
+        fn acknowledgment_counts_follow_mints_and_burns() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            assert!(!contract.is_acknowledged(cid(1)));
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            let _other = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            assert_eq!(contract.acknowledgment_count(cid(1)), 2);
+            assert!(contract.is_acknowledged(cid(1)));
+            set_caller(accounts.bob);
+            contract.burn(id).expect("owner may burn");
+            assert_eq!(contract.acknowledgment_count(cid(1)), 1);
+            assert!(contract.is_acknowledged(cid(1)));
+        }
+
+        #[ink::test]
+        fn check_invariants_reports_clean_state_and_pages() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            contract.mint(accounts.bob, cid(2), 0).expect("mint works");
+            let id = contract.mint(accounts.alice, cid(3), 0).expect("mint works");
+            contract.burn(id).expect("owner may burn");
+
+            let first_page = contract.check_invariants(0, 1).expect("owner may check");
+            assert_eq!(first_page.tokens_checked, 1);
+            assert_eq!(first_page.next_offset, Some(1));
+            let rest = contract.check_invariants(1, 100).expect("owner may check");
+            assert_eq!(rest.tokens_checked, 1);
+            assert_eq!(rest.next_offset, None);
+            for report in [first_page, rest] {
+                assert!(report.missing_owners.is_empty());
+                assert!(report.missing_acknowledgements.is_empty());
+                assert!(report.index_mismatches.is_empty());
+                assert!(report.owner_count_mismatches.is_empty());
+            }
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.check_invariants(0, 1), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn check_invariants_flags_inconsistencies() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            // simulate corruption left behind by a botched migration
+            contract.acknowledgements.remove(id);
+            contract.owned_tokens_count.insert(accounts.alice, &5);
+            let report = contract.check_invariants(0, 10).expect("owner may check");
+            assert_eq!(report.missing_acknowledgements, vec![id]);
+            assert_eq!(report.owner_count_mismatches, vec![accounts.alice]);
+        }
+
+        #[ink::test]
+        fn rebuild_owner_counts_repairs_drifted_counts() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            contract.mint(accounts.bob, cid(2), 0).expect("mint works");
+            contract.mint(accounts.alice, cid(3), 0).expect("mint works");
+            // simulate drift left behind by a botched migration
+            contract.owned_tokens_count.insert(accounts.alice, &7);
+            contract.owned_tokens_count.insert(accounts.bob, &0);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.rebuild_owner_counts(0, 10), Err(Error::NotOwner));
+            set_caller(accounts.alice);
+
+            // the first page only covers alice's token, but her recount
+            // spans the whole enumeration
+            assert_eq!(contract.rebuild_owner_counts(0, 1), Ok(Some(1)));
+            assert_eq!(contract.balance_of(accounts.alice), 2);
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert_eq!(contract.rebuild_owner_counts(1, 10), Ok(None));
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+            // a clean recount is a no-op
+            assert_eq!(contract.rebuild_owner_counts(0, 10), Ok(None));
+            assert_eq!(contract.balance_of(accounts.alice), 2);
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn burn_removes_token_and_enumeration() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let first = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            let second = contract.mint(accounts.alice, cid(2), 0).expect("mint works");
+            assert_eq!(contract.total_supply(), 2);
+
+            assert!(contract.burn(first).is_ok());
+            assert_eq!(contract.total_supply(), 1);
+            assert_eq!(contract.owner_of(first), None);
+            assert_eq!(contract.get_fragment_acknowledgment(first), None);
+            assert_eq!(contract.token_by_index(0), Some(second));
+            assert_eq!(contract.balance_of(accounts.alice), 1);
+        }
+
+        #[ink::test]
+        fn burn_requires_ownership_or_approval() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn(id), Err(BurnError::NotAllowed));
+            assert_eq!(
+                contract.burn_from(accounts.alice, id),
+                Err(BurnError::NotAllowed)
+            );
+            set_caller(accounts.alice);
+            contract.set_approval_for_all(accounts.bob, true).unwrap();
+            set_caller(accounts.bob);
+            assert!(contract.burn_from(accounts.alice, id).is_ok());
+            assert_eq!(contract.burn(id), Err(BurnError::TokenNotFound));
+        }
+
+        #[ink::test]
+        fn revoke_all_approvals_clears_every_grant() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let first = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            let second = contract.mint(accounts.alice, cid(2), 0).expect("mint works");
+            assert!(contract.approve(accounts.bob, first).is_ok());
+            assert!(contract.approve(accounts.charlie, second).is_ok());
+            contract.set_approval_for_all(accounts.django, true).unwrap();
+            contract.set_approval_for_all(accounts.eve, true).unwrap();
+
+            contract.revoke_all_approvals();
+            assert_eq!(contract.get_approved(first), None);
+            assert_eq!(contract.get_approved(second), None);
+            assert!(!contract.is_approved_for_all(accounts.alice, accounts.django));
+            assert!(!contract.is_approved_for_all(accounts.alice, accounts.eve));
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.bob, first),
+                Err(Error::NotApproved)
+            );
+        }
+
+        #[ink::test]
+        fn operator_restriction_gates_new_approvals() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            contract.set_operator_restriction(true).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_approval_for_all(accounts.eve, true),
+                Err(Error::OperatorNotAllowed)
+            );
+            assert_eq!(
+                contract.set_operator_restriction(false),
+                Err(Error::NotOwner)
+            );
+
+            set_caller(accounts.alice);
+            contract.allow_operator(accounts.eve).unwrap();
+            set_caller(accounts.bob);
+            assert!(contract.set_approval_for_all(accounts.eve, true).is_ok());
+
+            // de-listing does not touch live approvals, but revocation
+            // stays possible
+            set_caller(accounts.alice);
+            contract.disallow_operator(accounts.eve).unwrap();
+            assert!(contract.is_approved_for_all(accounts.bob, accounts.eve));
+            set_caller(accounts.bob);
+            assert!(contract.set_approval_for_all(accounts.eve, false).is_ok());
+        }
+
+        #[ink::test]
+        fn memos_are_owner_set_capped_and_cleared_on_transfer() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_memo(id, b"replica-3".to_vec()),
+                Err(Error::NotAllowed)
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                contract.set_memo(id, vec![0u8; 129]),
+                Err(Error::MemoTooLong)
+            );
+            assert!(contract.set_memo(id, b"replica-3".to_vec()).is_ok());
+            assert_eq!(contract.memo(id), Some(b"replica-3".to_vec()));
+            assert!(contract.set_memo(id, Vec::new()).is_ok());
+            assert_eq!(contract.memo(id), None);
+
+            assert!(contract.set_memo(id, b"eu-west shelf 2".to_vec()).is_ok());
+            assert!(contract.transfer(accounts.bob, id).is_ok());
+            assert_eq!(contract.memo(id), None);
+        }
+
+        #[ink::test]
+        fn validity_windows_expire_new_mints_only() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let evergreen = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            contract.set_validity_period(Some(2)).unwrap();
+            let expiring = contract.mint(accounts.bob, cid(2), 0).expect("mint works");
+
+            assert_eq!(contract.valid_until(evergreen), None);
+            assert!(contract.is_valid(evergreen));
+            assert!(contract.is_valid(expiring));
+
+            for _ in 0..3 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            assert!(contract.is_valid(evergreen));
+            assert!(!contract.is_valid(expiring));
+            // the token itself survives expiry
+            assert_eq!(contract.owner_of(expiring), Some(accounts.bob));
+            assert!(!contract.is_valid(99));
+        }
+
+        #[ink::test]
+        fn enumeration_by_owner() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let a = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            let b = contract.mint(accounts.bob, cid(2), 0).expect("mint works");
+            let c = contract.mint(accounts.alice, cid(3), 0).expect("mint works");
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 0), Some(a));
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 1), Some(c));
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 2), None);
+            assert_eq!(contract.token_of_owner_by_index(accounts.bob, 0), Some(b));
+        }
+
+        #[ink::test]
+        fn provenance_tracks_recent_holders_in_a_bounded_buffer() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            assert!(contract.transfer(accounts.bob, id).is_ok());
+            set_caller(accounts.bob);
+            assert!(contract.transfer(accounts.charlie, id).is_ok());
+            let holders: Vec<AccountId> = contract
+                .provenance(id)
+                .iter()
+                .map(|entry| entry.holder)
+                .collect();
+            assert_eq!(holders, vec![accounts.alice, accounts.bob, accounts.charlie]);
+
+            // bounce the token until the buffer wraps: the mint entry falls out
+            let mut holder = accounts.charlie;
+            for _ in 0..4 {
+                for next in [accounts.django, holder] {
+                    set_caller(holder);
+                    assert!(contract.transfer(next, id).is_ok());
+                    holder = next;
+                }
+            }
+            let entries = contract.provenance(id);
+            assert_eq!(entries.len(), 8);
+            assert_ne!(entries[0].holder, accounts.alice);
+        }
+
+        #[ink::test]
+        fn consolidation_merges_acknowledgements_into_a_summary() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let first = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            let second = contract.mint(accounts.bob, cid(2), 0).expect("mint works");
+            let third = contract.mint(accounts.alice, cid(3), 0).expect("mint works");
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.consolidate(vec![first]),
+                Err(Error::NothingToConsolidate)
+            );
+            // only the caller's own tokens can be merged, and a refused
+            // batch burns nothing
+            assert_eq!(
+                contract.consolidate(vec![first, third]),
+                Err(Error::NotAllowed)
+            );
+            assert_eq!(contract.owner_of(first), Some(accounts.bob));
+            let summary_id = contract
+                .consolidate(vec![first, second])
+                .expect("bob owns both");
+            assert_eq!(contract.owner_of(first), None);
+            assert_eq!(contract.owner_of(second), None);
+            assert_eq!(contract.owner_of(summary_id), Some(accounts.bob));
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+            let summary = contract.get_summary(summary_id).expect("summary exists");
+            assert_eq!(summary.cids, vec![cid(1), cid(2)]);
+            assert_eq!(summary.earliest_block, 0);
+            assert_eq!(summary.merged, 2);
+            // the summary keeps its fragments acknowledged
+            assert_eq!(contract.acknowledgment_count(cid(1)), 1);
+            assert_eq!(contract.acknowledgment_count(cid(2)), 1);
+            assert!(contract.burn(summary_id).is_ok());
+            assert_eq!(contract.acknowledgment_count(cid(1)), 0);
+            assert_eq!(contract.acknowledgment_count(cid(2)), 0);
+        }
+
+        #[ink::test]
+        fn usage_grants_expire_and_clear_on_transfer() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            set_caller(accounts.charlie);
+            assert_eq!(
+                contract.set_user(id, accounts.eve, 10),
+                Err(Error::NotApproved)
+            );
+            set_caller(accounts.bob);
+            assert!(contract.set_user(id, accounts.eve, 2).is_ok());
+            assert_eq!(contract.user_of(id), Some(accounts.eve));
+            assert_eq!(contract.user_expires(id), Some(2));
+            for _ in 0..3 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            // expired grants are reported as absent, but the record stays
+            assert_eq!(contract.user_of(id), None);
+            assert_eq!(contract.user_expires(id), Some(2));
+            assert!(contract.set_user(id, accounts.eve, 100).is_ok());
+            assert!(contract.transfer(accounts.charlie, id).is_ok());
+            assert_eq!(contract.user_of(id), None);
+            assert_eq!(contract.user_expires(id), None);
+        }
+
+        #[ink::test]
+        fn collateral_locks_freeze_transfers_and_burns() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            contract
+                .approve_locker(accounts.django)
+                .expect("alice owns the contract");
+            // an approved locker without a token approval cannot freeze it
+            set_caller(accounts.django);
+            assert_eq!(
+                contract.lock_as_collateral(id, accounts.django),
+                Err(Error::NotApproved)
+            );
+            set_caller(accounts.bob);
+            contract
+                .set_approval_for_all(accounts.django, true)
+                .expect("owners pick their lenders");
+            set_caller(accounts.django);
+            assert!(contract.lock_as_collateral(id, accounts.django).is_ok());
+            assert_eq!(contract.collateral_locker_of(id), Some(accounts.django));
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer(accounts.charlie, id),
+                Err(Error::TokenLocked)
+            );
+            assert_eq!(contract.burn(id), Err(BurnError::NotAllowed));
+            // only the holding locker can release
+            assert_eq!(contract.release_collateral(id), Err(Error::NotAllowed));
+            set_caller(accounts.django);
+            assert!(contract.release_collateral(id).is_ok());
+            set_caller(accounts.bob);
+            assert!(contract.transfer(accounts.charlie, id).is_ok());
+        }
+
+        #[ink::test]
+        fn locking_requires_locker_approval() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.bob, cid(1), 0).expect("mint works");
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.lock_as_collateral(id, accounts.bob),
+                Err(Error::NotLocker)
+            );
+        }
+
+        #[ink::test]
+        fn hook_registration_is_owner_only_and_idempotent() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut contract = FaNft::new();
+            set_caller(accounts.bob);
+            assert_eq!(contract.add_hook(accounts.eve), Err(Error::NotOwner));
+            set_caller(accounts.alice);
+            assert!(contract.add_hook(accounts.eve).is_ok());
+            assert!(contract.add_hook(accounts.eve).is_ok());
+            assert_eq!(contract.get_hooks(), vec![accounts.eve]);
+            assert!(contract.remove_hook(accounts.eve).is_ok());
+            assert!(contract.get_hooks().is_empty());
+        }
+
+        #[ink::test]
+        fn owner_enumeration_swaps_and_pops() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let a = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            let b = contract.mint(accounts.alice, cid(2), 0).expect("mint works");
+            let c = contract.mint(accounts.alice, cid(3), 0).expect("mint works");
+            // removing from the middle swaps the last token into the gap
+            assert!(contract.transfer(accounts.bob, b).is_ok());
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 0), Some(a));
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 1), Some(c));
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 2), None);
+            assert_eq!(contract.token_of_owner_by_index(accounts.bob, 0), Some(b));
+            // burning compacts the enumeration the same way
+            assert!(contract.burn(a).is_ok());
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 0), Some(c));
+            assert_eq!(contract.token_of_owner_by_index(accounts.alice, 1), None);
+        }
+
+        #[ink::test]
+        fn delegation_is_owner_only_and_time_bounded() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.delegate(id, accounts.charlie, 10),
+                Err(Error::NotAllowed)
+            );
+            set_caller(accounts.alice);
+            assert!(contract.delegate(id, accounts.charlie, 2).is_ok());
+            assert_eq!(
+                contract.delegation_of(id).map(|d| d.operator),
+                Some(accounts.charlie)
+            );
+            for _ in 0..3 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            // expired delegations are reported as absent
+            assert_eq!(contract.delegation_of(id), None);
+            assert!(contract.delegate(id, accounts.charlie, 100).is_ok());
+            assert!(contract.revoke_delegation(id).is_ok());
+            assert_eq!(contract.delegation_of(id), None);
+        }
+
+        #[ink::test]
+        fn delegation_clears_on_transfer() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(1), 0).expect("mint works");
+            assert!(contract.delegate(id, accounts.charlie, 100).is_ok());
+            assert!(contract.transfer(accounts.bob, id).is_ok());
+            assert_eq!(contract.delegation_of(id), None);
+        }
+
+        #[ink::test]
+        fn transfer_moves_token() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(7), 0).expect("mint works");
+            assert!(contract.transfer(accounts.bob, id).is_ok());
+            assert_eq!(contract.owner_of(id), Some(accounts.bob));
+            assert_eq!(contract.balance_of(accounts.alice), 0);
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn transfer_requires_approval() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(7), 0).expect("mint works");
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.alice, accounts.bob, id),
+                Err(Error::NotApproved)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_from_requires_from_to_be_the_owner() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let id = contract.mint(accounts.alice, cid(7), 0).expect("mint works");
+            contract.approve(accounts.bob, id).expect("alice owns the token");
+            // an approved caller naming someone other than the owner as
+            // `from` is refused before any bookkeeping moves
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer_from(accounts.charlie, accounts.bob, id),
+                Err(Error::NotOwner)
+            );
+            assert_eq!(contract.owner_of(id), Some(accounts.alice));
+            assert_eq!(contract.balance_of(accounts.alice), 1);
+            assert_eq!(contract.balance_of(accounts.charlie), 0);
+            // with the real owner as `from` the approval works as usual
+            assert!(contract
+                .transfer_from(accounts.alice, accounts.bob, id)
+                .is_ok());
+            assert_eq!(contract.owner_of(id), Some(accounts.bob));
+        }
+
+        #[ink::test]
+        fn transfer_fee_skims_payable_transfers() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            let first = contract.mint(accounts.alice, cid(7), 0).expect("mint works");
+            let second = contract.mint(accounts.alice, cid(8), 0).expect("mint works");
+            contract.set_transfer_fee(250).expect("alice owns the collection");
+            // fund the contract above the existential deposit so it can
+            // forward the seller's proceeds
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            let ed = ink::env::minimum_balance::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                callee,
+                ed + 100_000,
+            );
+
+            // 2.5% of the attached sale price accrues to the treasury
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(10_000);
+            assert!(contract.transfer(accounts.bob, first).is_ok());
+            assert_eq!(contract.treasury_balance(), 250);
+            assert_eq!(contract.fees_of_source(FeeSource::Transfers), 250);
+
+            // exempt callers are never skimmed
+            contract.set_fee_exempt(accounts.alice, true).expect("owner sets exemptions");
+            assert!(contract.is_fee_exempt(accounts.alice));
+            assert!(contract.transfer(accounts.bob, second).is_ok());
+            assert_eq!(contract.treasury_balance(), 250);
+        }
+
+        #[ink::test]
+        fn transfer_fee_configuration_is_owner_only_and_bounded() {
+            let accounts = accounts();
+            let mut contract = minting_contract();
+            assert_eq!(contract.set_transfer_fee(10_001), Err(Error::InvalidFee));
+            assert!(contract.set_transfer_fee(10_000).is_ok());
+            assert_eq!(contract.get_transfer_fee(), 10_000);
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_transfer_fee(0), Err(Error::NotOwner));
+            assert_eq!(
+                contract.set_fee_exempt(accounts.bob, true),
+                Err(Error::NotOwner)
+            );
+        }
+
+        #[ink::test]
+        fn admin_log_records_privileged_calls() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut contract = FaNft::new();
+            contract.set_minter(accounts.bob).expect("alice owns the collection");
+            contract.set_transfer_fee(50).expect("alice owns the collection");
+            let page = contract.admin_log(0, 10);
+            assert_eq!(page.entries.len(), 2);
+            assert_eq!(page.entries[0].message, b"set_minter".to_vec());
+            assert_eq!(page.entries[0].args, accounts.bob.encode());
+            assert_eq!(page.entries[1].message, b"set_transfer_fee".to_vec());
+            assert_eq!(page.next_offset, None);
+            // rejected callers leave no trace
+            set_caller(accounts.charlie);
+            assert_eq!(contract.set_minter(accounts.charlie), Err(Error::NotOwner));
+            assert_eq!(contract.admin_log(0, 10).entries.len(), 2);
+        }
+
+        #[ink::test]
+        fn timelocked_admin_actions_wait_out_their_delay() {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut contract = FaNft::new();
+            contract.set_timelock_delay(2).expect("alice owns the collection");
+            // direct setters refuse while the timelock is enabled
+            assert_eq!(contract.set_transfer_fee(100), Err(Error::TimelockRequired));
+            assert_eq!(
+                contract.set_treasury(accounts.bob),
+                Err(Error::TimelockRequired)
+            );
+            let id = contract
+                .schedule_admin_action(AdminAction::SetTransferFee(100))
+                .expect("the owner schedules actions");
+            assert_eq!(contract.pending_admin_actions().len(), 1);
+            assert_eq!(contract.execute_admin_action(id), Err(Error::ActionNotReady));
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(contract.execute_admin_action(id), Ok(()));
+            assert_eq!(contract.get_transfer_fee(), 100);
+
+            // cancelled actions never execute, and the queue is owner-only
+            let id = contract
+                .schedule_admin_action(AdminAction::SetTreasury(accounts.bob))
+                .expect("the owner schedules actions");
+            assert_eq!(contract.cancel_admin_action(id), Ok(()));
+            assert_eq!(contract.execute_admin_action(id), Err(Error::UnknownAction));
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.schedule_admin_action(AdminAction::SetTransferFee(0)),
+                Err(Error::NotOwner)
+            );
+        }
+    }
+}
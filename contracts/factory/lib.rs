@@ -0,0 +1,737 @@
+//! # Round Factory
+//!
+//! Deploys new [`fragments::fragments::FragmentsRound`] contracts from
+//! uploaded code hashes and keeps track of every round it has created, so
+//! that frontends have a single well-known address to query for rounds.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod factory {
+    use fragments::fragments::{Fragment, FragmentsRoundRef, RewardMode};
+    use fragments_types::CallFailure;
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::call::FromAccountId;
+    use ink::prelude::vec::Vec;
+    use ink::ToAccountId;
+    use ink::storage::Mapping;
+    use ownable::{Ownable, OwnableError, OwnershipData};
+    use registry::registry::RegistryRef;
+    use treasury::{FeeSource, TreasuryData};
+
+    /// An open dispute against a deployed round's MMR root.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Dispute {
+        /// The account that raised the dispute.
+        pub challenger: AccountId,
+        /// The block at which the dispute was raised.
+        pub raised_at: BlockNumber,
+        /// Challenger-supplied evidence of the inconsistency, e.g. two
+        /// valid proofs for conflicting leaves at the same position.
+        pub evidence: Vec<u8>,
+    }
+
+    #[ink(storage)]
+    pub struct Factory {
+        /// Ownership of the factory.
+        ownership: OwnershipData,
+        /// Code hash used to instantiate new rounds.
+        round_code_hash: Hash,
+        /// Code hash handed to each round to instantiate its NFT contract.
+        fa_nft_code_hash: Hash,
+        /// Every round deployed through this factory.
+        rounds: Vec<AccountId>,
+        /// Protocol fee charged per deployed round.
+        creation_fee: Balance,
+        /// Deposit a publisher must hold bonded before creating rounds.
+        publisher_bond: Balance,
+        /// Bonded deposit per registered publisher.
+        bonds: Mapping<AccountId, Balance>,
+        /// The publisher that created each round.
+        round_publishers: Mapping<AccountId, AccountId>,
+        /// Open disputes, keyed by the disputed round.
+        disputes: Mapping<AccountId, Dispute>,
+        /// Number of open disputes per publisher; a bonded deposit cannot
+        /// be withdrawn while any of the publisher's rounds is disputed.
+        open_disputes: Mapping<AccountId, u32>,
+        /// Fees accrued to each publisher and not yet withdrawn.
+        publisher_fees: Mapping<AccountId, Balance>,
+        /// When set, freshly deployed rounds are announced to this
+        /// discovery registry.
+        registry: Option<AccountId>,
+        /// Treasury accounting for the fees collected here.
+        treasury: TreasuryData,
+    }
+
+    // the codec derive casts each variant's index `usize as u8` and only
+    // allows the truncation lint on unit variants, so data-carrying
+    // enums have to spell the allow themselves
+    #[allow(clippy::cast_possible_truncation)]
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The caller is not the factory owner.
+        NotOwner,
+        /// The transferred value does not cover the round creation fee.
+        InsufficientFee,
+        /// The caller's bonded deposit does not meet the publisher bond.
+        InsufficientBond,
+        /// The given round was not deployed through this factory.
+        UnknownRound,
+        /// The round is already under dispute.
+        DisputeExists,
+        /// There is no open dispute against the given round.
+        UnknownDispute,
+        /// The bond cannot be withdrawn while a dispute is open.
+        BondLocked,
+        /// There are no accrued fees to withdraw.
+        NothingToWithdraw,
+        /// The native token transfer failed.
+        TransferFailed,
+        /// A call into a deployed round failed, classified into the
+        /// shared [`CallFailure`] categories so callers can tell
+        /// retryable failures from permanent ones.
+        RoundCall(CallFailure),
+    }
+
+    /// Emitted when a new round has been deployed.
+    #[ink(event)]
+    pub struct RoundCreated {
+        #[ink(topic)]
+        round: AccountId,
+        #[ink(topic)]
+        publisher: AccountId,
+        /// Number of fragments in the round's committed manifest.
+        total_fragments: u32,
+        /// Total size in bytes of the committed fragments, so
+        /// replication dashboards get authoritative numbers without
+        /// querying the round.
+        total_bytes: u64,
+    }
+
+    /// Emitted when a publisher registers or tops up their bond.
+    #[ink(event)]
+    pub struct PublisherRegistered {
+        #[ink(topic)]
+        publisher: AccountId,
+        bond: Balance,
+    }
+
+    /// Emitted when value is credited to a publisher's claimable balance.
+    #[ink(event)]
+    pub struct PublisherCredited {
+        #[ink(topic)]
+        publisher: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when a round's MMR root is disputed.
+    #[ink(event)]
+    pub struct RoundDisputed {
+        #[ink(topic)]
+        round: AccountId,
+        #[ink(topic)]
+        challenger: AccountId,
+    }
+
+    /// Emitted when a dispute has been resolved.
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        round: AccountId,
+        upheld: bool,
+        slashed: Balance,
+    }
+
+    impl Factory {
+        /// Creates a factory deploying rounds from `round_code_hash`, each
+        /// of which instantiates its NFT contract from `fa_nft_code_hash`.
+        /// Fees accrue to the caller until a treasury is configured.
+        #[ink(constructor)]
+        pub fn new(round_code_hash: Hash, fa_nft_code_hash: Hash) -> Self {
+            Self {
+                ownership: OwnershipData::new(Self::env().caller()),
+                round_code_hash,
+                fa_nft_code_hash,
+                rounds: Vec::new(),
+                creation_fee: 0,
+                publisher_bond: 0,
+                bonds: Mapping::default(),
+                round_publishers: Mapping::default(),
+                disputes: Mapping::default(),
+                open_disputes: Mapping::default(),
+                publisher_fees: Mapping::default(),
+                registry: None,
+                treasury: TreasuryData::new(Self::env().caller()),
+            }
+        }
+
+        /// Deploys a new round with the given commitment and fragment set.
+        /// The caller must hold the publisher bond (see
+        /// [`Self::register_publisher`]). The transferred balance, less the
+        /// creation fee, is forwarded to the round's reward pool. Ownership
+        /// of the round is handed to the caller.
+        #[ink(message, payable)]
+        pub fn create_round(
+            &mut self,
+            mmr_root: Vec<u8>,
+            fragments: Vec<Fragment>,
+            reward_per_claim: Balance,
+            reward_mode: RewardMode,
+            metadata_cid: Vec<u8>,
+        ) -> Result<AccountId, Error> {
+            let caller = self.env().caller();
+            if self.bonds.get(caller).unwrap_or(0) < self.publisher_bond {
+                return Err(Error::InsufficientBond);
+            }
+            let transferred = self.env().transferred_value();
+            if transferred < self.creation_fee {
+                return Err(Error::InsufficientFee);
+            }
+            if self.creation_fee > 0 {
+                self.treasury
+                    .record_fee(FeeSource::RoundCreation, self.creation_fee);
+            }
+            let salt = self.round_salt();
+            // sequential per-factory id, stamped into the round's events
+            let round_id = self.rounds.len() as u64;
+            let total_fragments = u32::try_from(fragments.len()).unwrap_or(u32::MAX);
+            let total_bytes = fragments
+                .iter()
+                .fold(0u64, |acc, fragment| acc.saturating_add(fragment.size));
+            let mut round = FragmentsRoundRef::new(
+                round_id,
+                mmr_root,
+                fragments,
+                reward_per_claim,
+                reward_mode,
+                self.fa_nft_code_hash,
+            )
+            .code_hash(self.round_code_hash)
+            .endowment(transferred.saturating_sub(self.creation_fee))
+            .salt_bytes(salt)
+            .instantiate();
+            match round.call_mut().transfer_ownership(caller).try_invoke() {
+                Ok(Ok(Ok(()))) => {}
+                Ok(Ok(Err(_refused))) => return Err(Error::RoundCall(CallFailure::Module)),
+                Ok(Err(_lang_error)) => return Err(Error::RoundCall(CallFailure::Decode)),
+                Err(error) => return Err(Error::RoundCall(CallFailure::from_env(error))),
+            }
+            let round_account = round.to_account_id();
+            self.rounds.push(round_account);
+            self.round_publishers.insert(round_account, &caller);
+            self.announce_round(round_account, caller, metadata_cid);
+            self.env().emit_event(RoundCreated {
+                round: round_account,
+                publisher: caller,
+                total_fragments,
+                total_bytes,
+            });
+            Ok(round_account)
+        }
+
+        /// Points round announcements at `registry`, or disables them when
+        /// `None`. The factory must be an authorized registrar there.
+        ///
+        /// Only callable by the factory owner.
+        #[ink(message)]
+        pub fn set_registry(&mut self, registry: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.registry = registry;
+            Ok(())
+        }
+
+        /// Returns the discovery registry rounds are announced to, if any.
+        #[ink(message)]
+        pub fn get_registry(&self) -> Option<AccountId> {
+            self.registry
+        }
+
+        /// Announces a freshly deployed round to the discovery registry.
+        /// Best effort: a misconfigured registry must not brick round
+        /// creation, so failures are swallowed.
+        fn announce_round(
+            &mut self,
+            round: AccountId,
+            publisher: AccountId,
+            metadata_cid: Vec<u8>,
+        ) {
+            let Some(registry) = self.registry else {
+                return;
+            };
+            let mut registry = RegistryRef::from_account_id(registry);
+            let _ = registry
+                .call_mut()
+                .register_round(round, publisher, metadata_cid)
+                .try_invoke();
+        }
+
+        /// Registers the caller as a publisher, bonding the transferred
+        /// value on top of any existing deposit. The bond backs the
+        /// integrity of the caller's MMR roots and is slashed if a round
+        /// is successfully disputed.
+        #[ink(message, payable)]
+        pub fn register_publisher(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let bond = self
+                .bonds
+                .get(caller)
+                .unwrap_or(0)
+                .saturating_add(self.env().transferred_value());
+            if bond < self.publisher_bond {
+                return Err(Error::InsufficientBond);
+            }
+            self.bonds.insert(caller, &bond);
+            self.env().emit_event(PublisherRegistered {
+                publisher: caller,
+                bond,
+            });
+            Ok(())
+        }
+
+        /// Returns the deposit `publisher` currently has bonded.
+        #[ink(message)]
+        pub fn bond_of(&self, publisher: AccountId) -> Balance {
+            self.bonds.get(publisher).unwrap_or(0)
+        }
+
+        /// Returns the caller's bonded deposit, ending their ability to
+        /// create rounds. Refused while any of their rounds is disputed.
+        #[ink(message)]
+        pub fn withdraw_bond(&mut self) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+            if self.open_disputes.get(caller).unwrap_or(0) > 0 {
+                return Err(Error::BondLocked);
+            }
+            let bond = self.bonds.get(caller).unwrap_or(0);
+            if bond == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.bonds.remove(caller);
+            self.env()
+                .transfer(caller, bond)
+                .map_err(|_| Error::TransferFailed)?;
+            Ok(bond)
+        }
+
+        /// Credits the transferred value to `publisher`'s claimable
+        /// balance. Called by round or marketplace contracts forwarding
+        /// proceeds — unsold claim slots, secondary fees — that belong to
+        /// the round's publisher rather than the protocol treasury.
+        #[ink(message, payable)]
+        pub fn credit_publisher(&mut self, publisher: AccountId) {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return;
+            }
+            self.publisher_fees.insert(
+                publisher,
+                &self.publisher_fees.get(publisher).unwrap_or(0).saturating_add(amount),
+            );
+            self.env().emit_event(PublisherCredited { publisher, amount });
+        }
+
+        /// Returns the fees `publisher` has accrued and not yet withdrawn.
+        #[ink(message)]
+        pub fn publisher_balance(&self, publisher: AccountId) -> Balance {
+            self.publisher_fees.get(publisher).unwrap_or(0)
+        }
+
+        /// Pays out the caller's accrued publisher fees.
+        #[ink(message)]
+        pub fn withdraw_publisher_fees(&mut self) -> Result<Balance, Error> {
+            let caller = self.env().caller();
+            let amount = self.publisher_fees.get(caller).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.publisher_fees.remove(caller);
+            self.env()
+                .transfer(caller, amount)
+                .map_err(|_| Error::TransferFailed)?;
+            Ok(amount)
+        }
+
+        /// Raises a dispute claiming `round`'s MMR root is inconsistent,
+        /// locking the publisher's bond until the factory owner rules on
+        /// the evidence. Open to anyone.
+        #[ink(message)]
+        pub fn dispute_round(
+            &mut self,
+            round: AccountId,
+            evidence: Vec<u8>,
+        ) -> Result<(), Error> {
+            let publisher = self
+                .round_publishers
+                .get(round)
+                .ok_or(Error::UnknownRound)?;
+            if self.disputes.contains(round) {
+                return Err(Error::DisputeExists);
+            }
+            let challenger = self.env().caller();
+            self.disputes.insert(
+                round,
+                &Dispute {
+                    challenger,
+                    raised_at: self.env().block_number(),
+                    evidence,
+                },
+            );
+            self.open_disputes.insert(
+                publisher,
+                &self.open_disputes.get(publisher).unwrap_or(0).saturating_add(1),
+            );
+            self.env().emit_event(RoundDisputed { round, challenger });
+            Ok(())
+        }
+
+        /// Returns the open dispute against `round`, if any.
+        #[ink(message)]
+        pub fn get_dispute(&self, round: AccountId) -> Option<Dispute> {
+            self.disputes.get(round)
+        }
+
+        /// Rules on the dispute against `round`. An upheld dispute slashes
+        /// the publisher's full bond to the challenger; a rejected one
+        /// simply unlocks the bond.
+        ///
+        /// Only callable by the factory owner.
+        #[ink(message)]
+        pub fn resolve_dispute(&mut self, round: AccountId, upheld: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let dispute = self.disputes.get(round).ok_or(Error::UnknownDispute)?;
+            let publisher = self
+                .round_publishers
+                .get(round)
+                .ok_or(Error::UnknownRound)?;
+            self.disputes.remove(round);
+            let open = self.open_disputes.get(publisher).unwrap_or(0);
+            self.open_disputes
+                .insert(publisher, &open.saturating_sub(1));
+            let mut slashed = 0;
+            if upheld {
+                slashed = self.bonds.get(publisher).unwrap_or(0);
+                if slashed > 0 {
+                    self.bonds.remove(publisher);
+                    self.env()
+                        .transfer(dispute.challenger, slashed)
+                        .map_err(|_| Error::TransferFailed)?;
+                }
+            }
+            self.env().emit_event(DisputeResolved {
+                round,
+                upheld,
+                slashed,
+            });
+            Ok(())
+        }
+
+        /// Sets the deposit publishers must bond before creating rounds.
+        ///
+        /// Only callable by the factory owner.
+        #[ink(message)]
+        pub fn set_publisher_bond(&mut self, publisher_bond: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.publisher_bond = publisher_bond;
+            Ok(())
+        }
+
+        /// Returns the deposit publishers must bond before creating rounds.
+        #[ink(message)]
+        pub fn get_publisher_bond(&self) -> Balance {
+            self.publisher_bond
+        }
+
+        /// Sets the protocol fee charged per deployed round.
+        ///
+        /// Only callable by the factory owner.
+        #[ink(message)]
+        pub fn set_creation_fee(&mut self, creation_fee: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.creation_fee = creation_fee;
+            Ok(())
+        }
+
+        /// Returns the protocol fee charged per deployed round.
+        #[ink(message)]
+        pub fn get_creation_fee(&self) -> Balance {
+            self.creation_fee
+        }
+
+        /// Points future treasury withdrawals at `treasury`.
+        ///
+        /// Only callable by the factory owner.
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.treasury.set_treasury(treasury);
+            Ok(())
+        }
+
+        /// Returns the fees accrued and not yet withdrawn.
+        #[ink(message)]
+        pub fn treasury_balance(&self) -> Balance {
+            self.treasury.balance()
+        }
+
+        /// Returns the lifetime fee total collected for `source`.
+        #[ink(message)]
+        pub fn fees_of_source(&self, source: FeeSource) -> Balance {
+            self.treasury.of_source(source)
+        }
+
+        /// Drains the accrued fees to the treasury address. Callable by
+        /// anyone, since the destination is fixed.
+        #[ink(message)]
+        pub fn withdraw_treasury(&mut self) -> Result<Balance, Error> {
+            let (recipient, amount) = self.treasury.withdraw();
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+            self.env()
+                .transfer(recipient, amount)
+                .map_err(|_| Error::TransferFailed)?;
+            Ok(amount)
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)
+        }
+
+        /// Returns every round deployed through this factory.
+        #[ink(message)]
+        pub fn get_rounds(&self) -> Vec<AccountId> {
+            self.rounds.clone()
+        }
+
+        /// Updates the code hashes used for future rounds.
+        ///
+        /// Only callable by the factory owner.
+        #[ink(message)]
+        pub fn set_code_hashes(
+            &mut self,
+            round_code_hash: Hash,
+            fa_nft_code_hash: Hash,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.round_code_hash = round_code_hash;
+            self.fa_nft_code_hash = fa_nft_code_hash;
+            Ok(())
+        }
+
+        /// Salt ensuring each deployed round gets a distinct address even
+        /// for identical constructor arguments.
+        fn round_salt(&self) -> Vec<u8> {
+            u32::try_from(self.rounds.len())
+                .unwrap_or(u32::MAX)
+                .to_le_bytes()
+                .to_vec()
+        }
+    }
+
+    impl Ownable for Factory {
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            self.ownership.owner()
+        }
+
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+            let event = self.ownership.transfer(self.env().caller(), new_owner)?;
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        fn set_value(value: Balance) {
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(value);
+        }
+
+        /// A factory owned by alice with a 100-unit publisher bond and
+        /// enough contract balance to pay refunds.
+        fn test_factory() -> Factory {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            set_value(0);
+            let mut factory = Factory::new(Hash::from([1u8; 32]), Hash::from([2u8; 32]));
+            factory
+                .set_publisher_bond(100)
+                .expect("alice owns the factory");
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            let ed = ink::env::minimum_balance::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                callee,
+                ed + 1_000,
+            );
+            factory
+        }
+
+        #[ink::test]
+        fn publisher_bonds_accumulate_and_refund() {
+            let accounts = accounts();
+            let mut factory = test_factory();
+            set_caller(accounts.bob);
+            // a deposit below the bond is refused and nothing is recorded
+            set_value(60);
+            assert_eq!(factory.register_publisher(), Err(Error::InsufficientBond));
+            assert_eq!(factory.bond_of(accounts.bob), 0);
+            set_value(100);
+            assert!(factory.register_publisher().is_ok());
+            // top-ups stack on the existing deposit
+            set_value(50);
+            assert!(factory.register_publisher().is_ok());
+            assert_eq!(factory.bond_of(accounts.bob), 150);
+            set_value(0);
+            assert_eq!(factory.withdraw_bond(), Ok(150));
+            assert_eq!(factory.bond_of(accounts.bob), 0);
+            assert_eq!(factory.withdraw_bond(), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn upheld_disputes_slash_the_locked_bond() {
+            let accounts = accounts();
+            let mut factory = test_factory();
+            set_caller(accounts.bob);
+            set_value(100);
+            assert!(factory.register_publisher().is_ok());
+            set_value(0);
+            // frank stands in for a round bob published
+            factory.round_publishers.insert(accounts.frank, &accounts.bob);
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                factory.dispute_round(accounts.django, ink::prelude::vec![1u8]),
+                Err(Error::UnknownRound)
+            );
+            assert!(factory
+                .dispute_round(accounts.frank, ink::prelude::vec![1u8])
+                .is_ok());
+            assert_eq!(
+                factory.dispute_round(accounts.frank, ink::prelude::vec![2u8]),
+                Err(Error::DisputeExists)
+            );
+            let dispute = factory.get_dispute(accounts.frank).expect("dispute open");
+            assert_eq!(dispute.challenger, accounts.charlie);
+
+            // the bond is locked while the dispute is open
+            set_caller(accounts.bob);
+            assert_eq!(factory.withdraw_bond(), Err(Error::BondLocked));
+
+            // only the owner rules on the evidence
+            assert_eq!(
+                factory.resolve_dispute(accounts.frank, true),
+                Err(Error::NotOwner)
+            );
+            set_caller(accounts.alice);
+            assert!(factory.resolve_dispute(accounts.frank, true).is_ok());
+            assert_eq!(factory.get_dispute(accounts.frank), None);
+            // the full bond went to the challenger
+            assert_eq!(factory.bond_of(accounts.bob), 0);
+            set_caller(accounts.bob);
+            assert_eq!(factory.withdraw_bond(), Err(Error::NothingToWithdraw));
+            set_caller(accounts.alice);
+            assert_eq!(
+                factory.resolve_dispute(accounts.frank, true),
+                Err(Error::UnknownDispute)
+            );
+        }
+
+        #[ink::test]
+        fn rejected_disputes_unlock_the_bond() {
+            let accounts = accounts();
+            let mut factory = test_factory();
+            set_caller(accounts.bob);
+            set_value(100);
+            assert!(factory.register_publisher().is_ok());
+            set_value(0);
+            factory.round_publishers.insert(accounts.frank, &accounts.bob);
+
+            set_caller(accounts.charlie);
+            assert!(factory
+                .dispute_round(accounts.frank, ink::prelude::vec![1u8])
+                .is_ok());
+            set_caller(accounts.alice);
+            assert!(factory.resolve_dispute(accounts.frank, false).is_ok());
+            // the bond survived the ruling and is withdrawable again
+            assert_eq!(factory.bond_of(accounts.bob), 100);
+            set_caller(accounts.bob);
+            assert_eq!(factory.withdraw_bond(), Ok(100));
+        }
+
+        #[ink::test]
+        fn publisher_fees_accrue_and_withdraw() {
+            let accounts = accounts();
+            let mut factory = test_factory();
+            // a zero-value credit is a no-op
+            factory.credit_publisher(accounts.bob);
+            assert_eq!(factory.publisher_balance(accounts.bob), 0);
+            set_value(40);
+            factory.credit_publisher(accounts.bob);
+            set_value(25);
+            factory.credit_publisher(accounts.bob);
+            set_value(0);
+            assert_eq!(factory.publisher_balance(accounts.bob), 65);
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                factory.withdraw_publisher_fees(),
+                Err(Error::NothingToWithdraw)
+            );
+            set_caller(accounts.bob);
+            assert_eq!(factory.withdraw_publisher_fees(), Ok(65));
+            assert_eq!(factory.publisher_balance(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn create_round_gates_on_bond_and_fee() {
+            let accounts = accounts();
+            let mut factory = test_factory();
+            set_caller(accounts.alice);
+            factory.set_creation_fee(10).expect("alice owns the factory");
+            set_caller(accounts.bob);
+            // an unbonded publisher is refused before any deployment
+            set_value(50);
+            assert_eq!(
+                factory.create_round(
+                    ink::prelude::vec![0u8; 32],
+                    Vec::new(),
+                    1,
+                    RewardMode::LumpSum,
+                    Vec::new(),
+                ),
+                Err(Error::InsufficientBond)
+            );
+            set_value(100);
+            assert!(factory.register_publisher().is_ok());
+            // so is a deployment not covering the creation fee
+            set_value(5);
+            assert_eq!(
+                factory.create_round(
+                    ink::prelude::vec![0u8; 32],
+                    Vec::new(),
+                    1,
+                    RewardMode::LumpSum,
+                    Vec::new(),
+                ),
+                Err(Error::InsufficientFee)
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Binding to the Ideal Network beacon's BLS verification chain
+//! extension.
+//!
+//! Verifying a BLS signature inside the contract's Wasm would cost
+//! prohibitive ref-time; runtimes shipping the beacon expose the
+//! verification as a chain-extension host function instead, keyed by
+//! the beacon's group public key held in the runtime. The pulse and
+//! error types are always available so other code can name them;
+//! [`verify`] itself is only compiled with the `ideal-beacon` feature,
+//! because calling the extension on a runtime that does not register it
+//! traps the contract.
+
+#[cfg(feature = "ideal-beacon")]
+use ink::env::chain_extension::ChainExtensionMethod;
+use ink::env::chain_extension::FromStatusCode;
+use ink::prelude::vec::Vec;
+
+/// Function id under which the Ideal runtime registers the beacon's BLS
+/// verification method: extension id `0x1d3a` ("id-ea"), method `1`.
+pub const VERIFY_FUNC_ID: u32 = 0x1d3a_0001;
+
+/// A pulse of the randomness beacon: the beacon round number and the
+/// group BLS signature over it. Signatures are verifiable by anyone
+/// holding the beacon's group public key, which gives on-chain records
+/// carrying a pulse an externally checkable wall-clock anchor.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BeaconPulse {
+    /// The beacon round the signature attests to.
+    pub round: u64,
+    /// The group BLS signature over the round.
+    pub signature: Vec<u8>,
+}
+
+/// Errors surfaced by the verification extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BeaconError {
+    /// The signature does not verify for the claimed beacon round.
+    InvalidSignature,
+    /// The extension returned an unknown status code; most likely the
+    /// runtime registers a different beacon version.
+    ExtensionFailed,
+}
+
+impl FromStatusCode for BeaconError {
+    fn from_status_code(status: u32) -> Result<(), Self> {
+        match status {
+            0 => Ok(()),
+            1 => Err(Self::InvalidSignature),
+            _ => Err(Self::ExtensionFailed),
+        }
+    }
+}
+
+/// Verifies `pulse` against the beacon's group public key through the
+/// runtime's chain extension.
+#[cfg(feature = "ideal-beacon")]
+pub fn verify(pulse: &BeaconPulse) -> Result<(), BeaconError> {
+    ChainExtensionMethod::build(VERIFY_FUNC_ID)
+        .input::<BeaconPulse>()
+        .output::<(), false>()
+        .handle_error_code::<BeaconError>()
+        .call(pulse)
+}
@@ -0,0 +1,101 @@
+//! Measures the MMR membership verification dominating `claim_fragment`,
+//! for proof sizes from 8 to 1M leaves and for both hash backends: the
+//! default `MergeLeaves` (the Keccak-256 host function, or the legacy
+//! in-Wasm SHA3-256 when the `legacy-sha3` feature is on) and a
+//! bench-local Keccak-256 reference of the same domain-separated
+//! scheme. Proofs are SCALE round-tripped into the contract's `Proof`
+//! type first, so the measured call is exactly the `verify` the claim
+//! path runs.
+//!
+//! Run with `cargo bench -p fragments`.
+
+use ckb_merkle_mountain_range::{util::MemStore, Merge, Result as MmrResult, MMR};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fragments::mmr::{Leaf, MergeLeaves, Proof, LEAF_DOMAIN, NODE_DOMAIN};
+use scale::{Decode, Encode};
+use sha3::{Digest, Keccak256};
+
+/// The contract backend's leaf for the `i`-th benchmark fragment: eight
+/// bytes of payload, the size committed alongside as on-chain.
+fn contract_leaf(i: u64) -> Leaf {
+    Leaf::from_parts(&i.to_le_bytes(), 8)
+}
+
+/// The reference backend's leaf, hashing the same domain-prefixed input.
+fn keccak_leaf(i: u64) -> Leaf {
+    let mut hasher = Keccak256::default();
+    hasher.update([LEAF_DOMAIN]);
+    hasher.update(i.to_le_bytes());
+    hasher.update(8u64.to_le_bytes());
+    Leaf(hasher.finalize().to_vec())
+}
+
+/// Bench-local Keccak-256 reference for the contract's `MergeLeaves`.
+struct KeccakMerge;
+
+impl Merge for KeccakMerge {
+    type Item = Leaf;
+
+    fn merge(lhs: &Self::Item, rhs: &Self::Item) -> MmrResult<Self::Item> {
+        let mut hasher = Keccak256::default();
+        hasher.update([NODE_DOMAIN]);
+        hasher.update(&lhs.0);
+        hasher.update(&rhs.0);
+        Ok(Leaf(hasher.finalize().to_vec()))
+    }
+}
+
+/// Builds an MMR of `leaf_count` leaves under merge strategy `M` and
+/// returns the contract-shaped proof for a mid-range leaf, with the
+/// root, position, and leaf to verify it against.
+fn proof_for<M>(leaf_count: u64, leaf: fn(u64) -> Leaf) -> (Proof<Leaf, M>, Leaf, u64, Leaf)
+where
+    M: Merge<Item = Leaf>,
+{
+    let store = MemStore::default();
+    let mut mmr = MMR::<Leaf, M, &MemStore<Leaf>>::new(0, &store);
+    let mut positions = Vec::with_capacity(leaf_count as usize);
+    for i in 0..leaf_count {
+        positions.push(mmr.push(leaf(i)).expect("push works"));
+    }
+    let root = mmr.get_root().expect("root exists");
+    let target = leaf_count / 2;
+    let raw = mmr
+        .gen_proof(vec![positions[target as usize]])
+        .expect("proof exists");
+    // decode from the wire shape the claim message receives, same as
+    // on-chain dispatch does, rather than going through `Proof::new`
+    let encoded = (raw.mmr_size(), raw.proof_items().to_vec()).encode();
+    let proof = Proof::<Leaf, M>::decode(&mut encoded.as_slice()).expect("proof decodes");
+    (proof, root, positions[target as usize], leaf(target))
+}
+
+fn mmr_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("claim_fragment/verify");
+    for leaf_count in [8u64, 64, 1_024, 16_384, 262_144, 1_048_576] {
+        let (proof, root, position, leaf) = proof_for::<MergeLeaves>(leaf_count, contract_leaf);
+        group.bench_with_input(
+            BenchmarkId::new("merge-leaves", leaf_count),
+            &leaf_count,
+            |bencher, _| {
+                bencher.iter(|| {
+                    assert!(proof.verify(root.clone(), vec![(position, leaf.clone())]))
+                })
+            },
+        );
+        let (proof, root, position, leaf) = proof_for::<KeccakMerge>(leaf_count, keccak_leaf);
+        group.bench_with_input(
+            BenchmarkId::new("keccak-256-reference", leaf_count),
+            &leaf_count,
+            |bencher, _| {
+                bencher.iter(|| {
+                    assert!(proof.verify(root.clone(), vec![(position, leaf.clone())]))
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, mmr_verification);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use std::process::Command;
+
+/// Bakes the source commit into the build for `contract_info`, so a
+/// deployed round can report exactly which revision it was built from.
+fn main() {
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_owned())
+        .unwrap_or_else(|| "unknown".to_owned());
+    println!("cargo:rustc-env=FRAGMENTS_GIT_COMMIT={commit}");
+}
@@ -0,0 +1,344 @@
+//! # Insurance Pool
+//!
+//! A pooled fund claimers pay premiums into. When a slashing or dispute
+//! wipes a covered account's deposit or rewards and governance finds no
+//! fault against them, the pool reimburses a configured portion of the
+//! loss. Claim resolution is delegated to the pool owner, expected to be a
+//! governance account or council, so the pool composes with the round's
+//! dispute subsystem without hard-wiring it.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod insurance_pool {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use ownable::{Ownable, OwnableError, OwnershipData};
+
+    /// Identifier of a filed reimbursement claim.
+    pub type ClaimId = u32;
+
+    /// Lifecycle of a reimbursement claim.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ClaimStatus {
+        /// Filed and awaiting a governance decision.
+        Pending,
+        /// Approved; carries the amount actually reimbursed.
+        Approved { paid: Balance },
+        /// Rejected by governance.
+        Rejected,
+    }
+
+    /// A reimbursement claim filed by a covered account.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Claim {
+        /// The covered account that suffered the loss.
+        pub claimant: AccountId,
+        /// The amount lost to the slashing or dispute.
+        pub amount_lost: Balance,
+        /// The block at which the claim was filed.
+        pub filed_at: BlockNumber,
+        /// Current status of the claim.
+        pub status: ClaimStatus,
+    }
+
+    #[ink(storage)]
+    pub struct InsurancePool {
+        /// Ownership of the pool; the owner (normally a governance account)
+        /// resolves claims.
+        ownership: OwnershipData,
+        /// Minimum cumulative premium for coverage.
+        min_premium: Balance,
+        /// Percentage of an approved loss the pool reimburses.
+        payout_percent: u8,
+        /// Cumulative premium paid per account.
+        premiums: Mapping<AccountId, Balance>,
+        /// Filed claims by id.
+        claims: Mapping<ClaimId, Claim>,
+        /// The id the next filed claim receives.
+        next_claim_id: ClaimId,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The caller is not the pool owner.
+        NotOwner,
+        /// The caller has not paid enough premium to be covered.
+        NotCovered,
+        /// No claim with the given id exists.
+        UnknownClaim,
+        /// The claim has already been resolved.
+        ClaimAlreadyResolved,
+        /// The pool cannot cover the payout without being reaped.
+        InsufficientPool,
+        /// The native token transfer failed.
+        TransferFailed,
+    }
+
+    /// Emitted when an account pays premium into the pool.
+    #[ink(event)]
+    pub struct PremiumPaid {
+        #[ink(topic)]
+        account: AccountId,
+        amount: Balance,
+        total: Balance,
+    }
+
+    /// Emitted when a covered account files a reimbursement claim. The
+    /// evidence bytes are surfaced for the governance vote.
+    #[ink(event)]
+    pub struct ClaimFiled {
+        #[ink(topic)]
+        claim_id: ClaimId,
+        #[ink(topic)]
+        claimant: AccountId,
+        amount_lost: Balance,
+        evidence: Vec<u8>,
+    }
+
+    /// Emitted when governance resolves a claim.
+    #[ink(event)]
+    pub struct ClaimResolved {
+        #[ink(topic)]
+        claim_id: ClaimId,
+        approved: bool,
+        paid: Balance,
+    }
+
+    impl InsurancePool {
+        /// Creates a pool owned by the caller. Accounts with at least
+        /// `min_premium` paid in are covered; approved losses are
+        /// reimbursed at `payout_percent` percent. The transferred balance
+        /// seeds the fund.
+        #[ink(constructor, payable)]
+        pub fn new(min_premium: Balance, payout_percent: u8) -> Self {
+            Self {
+                ownership: OwnershipData::new(Self::env().caller()),
+                min_premium,
+                payout_percent: payout_percent.min(100),
+                premiums: Mapping::default(),
+                claims: Mapping::default(),
+                next_claim_id: 0,
+            }
+        }
+
+        /// Pays the transferred value into the pool as premium for the
+        /// caller.
+        #[ink(message, payable)]
+        pub fn pay_premium(&mut self) {
+            let account = self.env().caller();
+            let amount = self.env().transferred_value();
+            let total = self
+                .premiums
+                .get(account)
+                .unwrap_or(0)
+                .saturating_add(amount);
+            self.premiums.insert(account, &total);
+            self.env().emit_event(PremiumPaid {
+                account,
+                amount,
+                total,
+            });
+        }
+
+        /// Files a reimbursement claim for `amount_lost`, attaching
+        /// `evidence` of the slashing or dispute for the governance vote.
+        /// Only covered accounts may file.
+        #[ink(message)]
+        pub fn file_claim(
+            &mut self,
+            amount_lost: Balance,
+            evidence: Vec<u8>,
+        ) -> Result<ClaimId, Error> {
+            let claimant = self.env().caller();
+            if self.premiums.get(claimant).unwrap_or(0) < self.min_premium {
+                return Err(Error::NotCovered);
+            }
+            let claim_id = self.next_claim_id;
+            self.next_claim_id = claim_id.saturating_add(1);
+            self.claims.insert(
+                claim_id,
+                &Claim {
+                    claimant,
+                    amount_lost,
+                    filed_at: self.env().block_number(),
+                    status: ClaimStatus::Pending,
+                },
+            );
+            self.env().emit_event(ClaimFiled {
+                claim_id,
+                claimant,
+                amount_lost,
+                evidence,
+            });
+            Ok(claim_id)
+        }
+
+        /// Resolves a pending claim. On approval the claimant is paid the
+        /// configured portion of their loss from the pool.
+        ///
+        /// Only callable by the pool owner (governance).
+        #[ink(message)]
+        pub fn resolve_claim(&mut self, claim_id: ClaimId, approve: bool) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)?;
+            let mut claim = self.claims.get(claim_id).ok_or(Error::UnknownClaim)?;
+            if claim.status != ClaimStatus::Pending {
+                return Err(Error::ClaimAlreadyResolved);
+            }
+            let mut paid = 0;
+            if approve {
+                paid = claim
+                    .amount_lost
+                    .saturating_mul(u128::from(self.payout_percent))
+                    / 100;
+                let available = self
+                    .env()
+                    .balance()
+                    .saturating_sub(self.env().minimum_balance());
+                if paid > available {
+                    return Err(Error::InsufficientPool);
+                }
+                self.env()
+                    .transfer(claim.claimant, paid)
+                    .map_err(|_| Error::TransferFailed)?;
+                claim.status = ClaimStatus::Approved { paid };
+            } else {
+                claim.status = ClaimStatus::Rejected;
+            }
+            self.claims.insert(claim_id, &claim);
+            self.env().emit_event(ClaimResolved {
+                claim_id,
+                approved: approve,
+                paid,
+            });
+            Ok(())
+        }
+
+        /// Returns the claim filed under `claim_id`, if any.
+        #[ink(message)]
+        pub fn get_claim(&self, claim_id: ClaimId) -> Option<Claim> {
+            self.claims.get(claim_id)
+        }
+
+        /// Returns the cumulative premium `account` has paid.
+        #[ink(message)]
+        pub fn premium_of(&self, account: AccountId) -> Balance {
+            self.premiums.get(account).unwrap_or(0)
+        }
+
+        /// Returns `true` if `account` has paid enough premium to be
+        /// covered.
+        #[ink(message)]
+        pub fn is_covered(&self, account: AccountId) -> bool {
+            self.premiums.get(account).unwrap_or(0) >= self.min_premium
+        }
+    }
+
+    impl Ownable for InsurancePool {
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            self.ownership.owner()
+        }
+
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+            let event = self.ownership.transfer(self.env().caller(), new_owner)?;
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        fn funded_pool() -> InsurancePool {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let pool = InsurancePool::new(100, 80);
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            let ed = ink::env::minimum_balance::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                callee,
+                ed + 10_000,
+            );
+            pool
+        }
+
+        #[ink::test]
+        fn coverage_requires_minimum_premium() {
+            let accounts = accounts();
+            let mut pool = funded_pool();
+            set_caller(accounts.bob);
+            assert_eq!(
+                pool.file_claim(500, Vec::new()),
+                Err(Error::NotCovered)
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            pool.pay_premium();
+            assert!(pool.is_covered(accounts.bob));
+            assert_eq!(pool.premium_of(accounts.bob), 100);
+            assert!(pool.file_claim(500, Vec::new()).is_ok());
+        }
+
+        #[ink::test]
+        fn approved_claims_pay_the_configured_portion() {
+            let accounts = accounts();
+            let mut pool = funded_pool();
+            set_caller(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            pool.pay_premium();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            let claim_id = pool.file_claim(500, Vec::new()).expect("covered");
+
+            assert_eq!(pool.resolve_claim(claim_id, true), Err(Error::NotOwner));
+            set_caller(accounts.alice);
+            assert!(pool.resolve_claim(claim_id, true).is_ok());
+            // 80% of 500
+            assert_eq!(
+                pool.get_claim(claim_id).map(|claim| claim.status),
+                Some(ClaimStatus::Approved { paid: 400 })
+            );
+            assert_eq!(
+                pool.resolve_claim(claim_id, false),
+                Err(Error::ClaimAlreadyResolved)
+            );
+            assert_eq!(pool.resolve_claim(9, true), Err(Error::UnknownClaim));
+        }
+
+        #[ink::test]
+        fn rejected_claims_pay_nothing() {
+            let accounts = accounts();
+            let mut pool = funded_pool();
+            set_caller(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            pool.pay_premium();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            let claim_id = pool.file_claim(500, Vec::new()).expect("covered");
+            set_caller(accounts.alice);
+            assert!(pool.resolve_claim(claim_id, false).is_ok());
+            assert_eq!(
+                pool.get_claim(claim_id).map(|claim| claim.status),
+                Some(ClaimStatus::Rejected)
+            );
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! # Key Escrow
+//!
+//! Escrow for the decryption keys of timelock-encrypted fragments. A round
+//! publisher deposits the encrypted key material for each fragment together
+//! with a commitment to the plaintext key; once the release block is
+//! reached, anyone holding the decrypted key (obtained from the beacon
+//! signature off-chain) can publish it on-chain, where it is checked
+//! against the commitment and announced to acknowledgers by event. A
+//! bounty can be attached to each escrow and is paid to the first
+//! account to publish the matching key, so timely reveals do not hang
+//! on the publisher's liveness alone. This completes the "fragments
+//! released over time" story of the round contract.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod key_escrow {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    pub use fragments_types::FragmentCid;
+
+    /// An escrowed key for one fragment of one round.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Escrow {
+        /// The account that deposited the escrow.
+        pub publisher: AccountId,
+        /// The timelock-encrypted key material, decryptable once the
+        /// beacon signature for the release round becomes available.
+        pub ciphertext: Vec<u8>,
+        /// Keccak-256 commitment to the plaintext key, checked on publish.
+        pub key_commitment: [u8; 32],
+        /// The block from which the key may be published.
+        pub release_block: BlockNumber,
+        /// The plaintext key, once published.
+        pub key: Option<Vec<u8>>,
+        /// The reveal bounty held for this escrow, paid to the first
+        /// account to publish the matching key. Zero once paid out.
+        pub bounty: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct KeyEscrow {
+        /// Escrowed keys, keyed by the round contract and the fragment cid.
+        escrows: Mapping<(AccountId, FragmentCid), Escrow>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// An escrow for this round and fragment already exists.
+        EscrowExists,
+        /// No escrow is recorded for this round and fragment.
+        UnknownEscrow,
+        /// The escrow's release block has not been reached yet.
+        NotReleased,
+        /// The escrowed key has already been published.
+        KeyAlreadyPublished,
+        /// The submitted key does not match the deposited commitment.
+        KeyMismatch,
+        /// The bounty payout transfer failed.
+        TransferFailed,
+    }
+
+    /// Emitted when a publisher deposits an escrowed key.
+    #[ink(event)]
+    pub struct KeyDeposited {
+        #[ink(topic)]
+        round: AccountId,
+        #[ink(topic)]
+        cid: FragmentCid,
+        publisher: AccountId,
+        release_block: BlockNumber,
+    }
+
+    /// Emitted when an escrowed key is published, carrying the plaintext
+    /// key so acknowledgers can decrypt their fragment without any further
+    /// query.
+    #[ink(event)]
+    pub struct KeyPublished {
+        #[ink(topic)]
+        round: AccountId,
+        #[ink(topic)]
+        cid: FragmentCid,
+        revealer: AccountId,
+        key: Vec<u8>,
+        /// The reveal bounty paid to the revealer, zero when none was
+        /// funded.
+        bounty: Balance,
+    }
+
+    impl KeyEscrow {
+        /// Creates an empty escrow contract.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                escrows: Mapping::default(),
+            }
+        }
+
+        /// Deposits the timelock-encrypted key for fragment `cid` of
+        /// `round`, committing to the plaintext with `key_commitment`
+        /// (the Keccak-256 digest of the key). The key may be published
+        /// from `release_block` on. Any transferred value becomes the
+        /// reveal bounty, paid to whoever publishes the key first.
+        #[ink(message, payable)]
+        pub fn deposit_key(
+            &mut self,
+            round: AccountId,
+            cid: FragmentCid,
+            ciphertext: Vec<u8>,
+            key_commitment: [u8; 32],
+            release_block: BlockNumber,
+        ) -> Result<(), Error> {
+            if self.escrows.contains((round, cid.clone())) {
+                return Err(Error::EscrowExists);
+            }
+            let publisher = self.env().caller();
+            self.escrows.insert(
+                (round, cid.clone()),
+                &Escrow {
+                    publisher,
+                    ciphertext,
+                    key_commitment,
+                    release_block,
+                    key: None,
+                    bounty: self.env().transferred_value(),
+                },
+            );
+            self.env().emit_event(KeyDeposited {
+                round,
+                cid,
+                publisher,
+                release_block,
+            });
+            Ok(())
+        }
+
+        /// Publishes the plaintext `key` for fragment `cid` of `round`,
+        /// together with the decryption `evidence` (typically the beacon
+        /// signature for the release round, kept for off-chain auditing).
+        /// Callable by anyone once the release block is reached; the key
+        /// must match the deposited commitment.
+        #[ink(message)]
+        pub fn publish_key(
+            &mut self,
+            round: AccountId,
+            cid: FragmentCid,
+            key: Vec<u8>,
+            _evidence: Vec<u8>,
+        ) -> Result<(), Error> {
+            let mut escrow = self
+                .escrows
+                .get((round, cid.clone()))
+                .ok_or(Error::UnknownEscrow)?;
+            if self.env().block_number() < escrow.release_block {
+                return Err(Error::NotReleased);
+            }
+            if escrow.key.is_some() {
+                return Err(Error::KeyAlreadyPublished);
+            }
+            let mut digest = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Keccak256>(&key, &mut digest);
+            if digest != escrow.key_commitment {
+                return Err(Error::KeyMismatch);
+            }
+            let revealer = self.env().caller();
+            let bounty = escrow.bounty;
+            // the bounty is consumed in storage before the transfer, and
+            // a failed payout reverts the publication with it
+            escrow.key = Some(key.clone());
+            escrow.bounty = 0;
+            self.escrows.insert((round, cid.clone()), &escrow);
+            if bounty > 0 {
+                self.env()
+                    .transfer(revealer, bounty)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+            self.env().emit_event(KeyPublished {
+                round,
+                cid,
+                revealer,
+                key,
+                bounty,
+            });
+            Ok(())
+        }
+
+        /// Adds the transferred value to the reveal bounty of an
+        /// existing escrow. Typically called by the round publisher —
+        /// out of the round's pool — to price a timely reveal after the
+        /// fact; anyone else may sweeten it too. Returns the new bounty.
+        #[ink(message, payable)]
+        pub fn fund_reveal(
+            &mut self,
+            round: AccountId,
+            cid: FragmentCid,
+        ) -> Result<Balance, Error> {
+            let mut escrow = self
+                .escrows
+                .get((round, cid.clone()))
+                .ok_or(Error::UnknownEscrow)?;
+            if escrow.key.is_some() {
+                return Err(Error::KeyAlreadyPublished);
+            }
+            escrow.bounty = escrow.bounty.saturating_add(self.env().transferred_value());
+            self.escrows.insert((round, cid), &escrow);
+            Ok(escrow.bounty)
+        }
+
+        /// Returns the reveal bounty currently held for fragment `cid`
+        /// of `round`, zero once paid or when none was funded.
+        #[ink(message)]
+        pub fn reveal_bounty(&self, round: AccountId, cid: FragmentCid) -> Balance {
+            self.escrows
+                .get((round, cid))
+                .map(|escrow| escrow.bounty)
+                .unwrap_or(0)
+        }
+
+        /// Returns the escrow recorded for fragment `cid` of `round`, if
+        /// any.
+        #[ink(message)]
+        pub fn get_escrow(&self, round: AccountId, cid: FragmentCid) -> Option<Escrow> {
+            self.escrows.get((round, cid))
+        }
+
+        /// Returns the published plaintext key for fragment `cid` of
+        /// `round`, once it has been revealed.
+        #[ink(message)]
+        pub fn get_key(&self, round: AccountId, cid: FragmentCid) -> Option<Vec<u8>> {
+            self.escrows.get((round, cid)).and_then(|escrow| escrow.key)
+        }
+    }
+
+    impl Default for KeyEscrow {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn commitment(key: &[u8]) -> [u8; 32] {
+            let mut digest = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Keccak256>(key, &mut digest);
+            digest
+        }
+
+        fn cid(n: u8) -> FragmentCid {
+            // raw-codec CIDv1-style bytes, distinct per fragment
+            ink::prelude::vec![0x01, 0x55, n]
+        }
+
+        #[ink::test]
+        fn deposit_and_publish_round_trip() {
+            let accounts = accounts();
+            let mut escrow = KeyEscrow::new();
+            let key = b"the-decryption-key".to_vec();
+            assert!(escrow
+                .deposit_key(accounts.django, cid(1), b"ciphertext".to_vec(), commitment(&key), 0)
+                .is_ok());
+            assert_eq!(
+                escrow.deposit_key(accounts.django, cid(1), Vec::new(), commitment(&key), 0),
+                Err(Error::EscrowExists)
+            );
+            assert_eq!(escrow.get_key(accounts.django, cid(1)), None);
+            assert!(escrow
+                .publish_key(accounts.django, cid(1), key.clone(), b"beacon-sig".to_vec())
+                .is_ok());
+            assert_eq!(escrow.get_key(accounts.django, cid(1)), Some(key.clone()));
+            assert_eq!(
+                escrow.publish_key(accounts.django, cid(1), key, Vec::new()),
+                Err(Error::KeyAlreadyPublished)
+            );
+        }
+
+        #[ink::test]
+        fn reveals_pay_the_first_publisher() {
+            let accounts = accounts();
+            let mut escrow = KeyEscrow::new();
+            let key = b"the-decryption-key".to_vec();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(25);
+            assert!(escrow
+                .deposit_key(accounts.django, cid(1), Vec::new(), commitment(&key), 0)
+                .is_ok());
+            assert_eq!(escrow.reveal_bounty(accounts.django, cid(1)), 25);
+            // anyone can sweeten the pot while the key is unpublished
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(15);
+            assert_eq!(escrow.fund_reveal(accounts.django, cid(1)), Ok(40));
+            assert_eq!(
+                escrow.fund_reveal(accounts.django, cid(2)),
+                Err(Error::UnknownEscrow)
+            );
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            let callee = ink::env::test::callee::<ink::env::DefaultEnvironment>();
+            let ed = ink::env::minimum_balance::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
+                callee,
+                ed + 1_000,
+            );
+            assert!(escrow
+                .publish_key(accounts.django, cid(1), key, Vec::new())
+                .is_ok());
+            // the bounty is consumed with the publication
+            assert_eq!(escrow.reveal_bounty(accounts.django, cid(1)), 0);
+            assert_eq!(
+                escrow.fund_reveal(accounts.django, cid(1)),
+                Err(Error::KeyAlreadyPublished)
+            );
+        }
+
+        #[ink::test]
+        fn publish_respects_release_block_and_commitment() {
+            let accounts = accounts();
+            let mut escrow = KeyEscrow::new();
+            let key = b"the-decryption-key".to_vec();
+            assert!(escrow
+                .deposit_key(accounts.django, cid(1), Vec::new(), commitment(&key), 5)
+                .is_ok());
+            assert_eq!(
+                escrow.publish_key(accounts.django, cid(1), key.clone(), Vec::new()),
+                Err(Error::NotReleased)
+            );
+            for _ in 0..5 {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            }
+            assert_eq!(
+                escrow.publish_key(accounts.django, cid(1), b"wrong".to_vec(), Vec::new()),
+                Err(Error::KeyMismatch)
+            );
+            assert_eq!(
+                escrow.publish_key(accounts.eve, cid(1), key.clone(), Vec::new()),
+                Err(Error::UnknownEscrow)
+            );
+            assert!(escrow.publish_key(accounts.django, cid(1), key, Vec::new()).is_ok());
+        }
+    }
+}
@@ -0,0 +1,466 @@
+//! # Round Registry
+//!
+//! A lightweight on-chain directory of deployed rounds, auto-populated by
+//! the factory, so wallets and frontends can discover active rounds with
+//! paginated queries instead of running an indexer. The registry caches
+//! each round's lifecycle status and metadata cid; status updates are
+//! pushed by the registering contract or the round's publisher.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod registry {
+    use claim_history::{ClaimHistory, HistoryError};
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use ownable::{Ownable, OwnableError, OwnershipData};
+
+    /// Sequential identifier of a registered round.
+    pub type RoundId = u32;
+
+    /// Cached lifecycle status of a round. Mirrors the round contract's
+    /// own status enum; the registry copy is a cache, not the source of
+    /// truth.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum RoundStatus {
+        /// The round has been deployed but claims are not yet accepted.
+        Pending,
+        /// Claims are being accepted.
+        Active,
+        /// The round has been closed; claims are frozen.
+        Closed,
+    }
+
+    /// One registered round.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RoundEntry {
+        /// Address of the round contract.
+        pub round: AccountId,
+        /// The publisher that created the round.
+        pub publisher: AccountId,
+        /// Cached lifecycle status.
+        pub status: RoundStatus,
+        /// Cid of the round's off-chain metadata document.
+        pub metadata_cid: Vec<u8>,
+        /// The block at which the round was registered.
+        pub registered_at: BlockNumber,
+    }
+
+    /// One accepted claim pushed by a registered round: an entry in an
+    /// account's cross-round résumé.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct ClaimHistoryEntry {
+        /// Address of the round that accepted the claim.
+        pub round: AccountId,
+        /// Identifier of the claimed fragment's content.
+        pub cid: Vec<u8>,
+        /// The block at which the claim was pushed to the registry.
+        pub block: BlockNumber,
+        /// The immediate reward accrual the claim caused, as reported by
+        /// the round.
+        pub reward: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct Registry {
+        /// Ownership of the registry.
+        ownership: OwnershipData,
+        /// Accounts allowed to register rounds and push status updates
+        /// (normally factory contracts).
+        registrars: Mapping<AccountId, ()>,
+        /// Registered rounds by sequential id.
+        rounds: Mapping<RoundId, RoundEntry>,
+        /// Reverse lookup from round address to id.
+        round_ids: Mapping<AccountId, RoundId>,
+        /// The id the next registered round receives.
+        next_round_id: RoundId,
+        /// Accepted claims per account, across every registered round,
+        /// in the order they were pushed.
+        history: Mapping<AccountId, Vec<ClaimHistoryEntry>>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Copy, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The caller is not the registry owner.
+        NotOwner,
+        /// The caller is not an authorized registrar.
+        NotRegistrar,
+        /// No round is registered under the given id.
+        UnknownRound,
+        /// The round address is already registered.
+        AlreadyRegistered,
+    }
+
+    /// Emitted when a round has been registered.
+    #[ink(event)]
+    pub struct RoundRegistered {
+        #[ink(topic)]
+        round_id: RoundId,
+        #[ink(topic)]
+        round: AccountId,
+        #[ink(topic)]
+        publisher: AccountId,
+    }
+
+    /// Emitted when a round's cached status has been updated.
+    #[ink(event)]
+    pub struct StatusUpdated {
+        #[ink(topic)]
+        round_id: RoundId,
+        status: RoundStatus,
+    }
+
+    /// Emitted when a registered round pushes an accepted claim.
+    #[ink(event)]
+    pub struct ClaimNoted {
+        #[ink(topic)]
+        round: AccountId,
+        #[ink(topic)]
+        account: AccountId,
+        cid: Vec<u8>,
+    }
+
+    impl Registry {
+        /// Creates an empty registry owned by the caller.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                ownership: OwnershipData::new(Self::env().caller()),
+                registrars: Mapping::default(),
+                rounds: Mapping::default(),
+                round_ids: Mapping::default(),
+                next_round_id: 0,
+                history: Mapping::default(),
+            }
+        }
+
+        /// Authorizes `registrar` to register rounds.
+        ///
+        /// Only callable by the registry owner.
+        #[ink(message)]
+        pub fn add_registrar(&mut self, registrar: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.registrars.insert(registrar, &());
+            Ok(())
+        }
+
+        /// Revokes `registrar`'s authorization.
+        ///
+        /// Only callable by the registry owner.
+        #[ink(message)]
+        pub fn remove_registrar(&mut self, registrar: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.registrars.remove(registrar);
+            Ok(())
+        }
+
+        /// Returns `true` if `account` may register rounds.
+        #[ink(message)]
+        pub fn is_registrar(&self, account: AccountId) -> bool {
+            self.registrars.contains(account)
+        }
+
+        /// Registers a freshly deployed round and returns its id. New
+        /// rounds start in the `Pending` status.
+        ///
+        /// Only callable by authorized registrars.
+        #[ink(message)]
+        pub fn register_round(
+            &mut self,
+            round: AccountId,
+            publisher: AccountId,
+            metadata_cid: Vec<u8>,
+        ) -> Result<RoundId, Error> {
+            if !self.registrars.contains(self.env().caller()) {
+                return Err(Error::NotRegistrar);
+            }
+            if self.round_ids.contains(round) {
+                return Err(Error::AlreadyRegistered);
+            }
+            let round_id = self.next_round_id;
+            self.rounds.insert(
+                round_id,
+                &RoundEntry {
+                    round,
+                    publisher,
+                    status: RoundStatus::Pending,
+                    metadata_cid,
+                    registered_at: self.env().block_number(),
+                },
+            );
+            self.round_ids.insert(round, &round_id);
+            self.next_round_id = round_id.saturating_add(1);
+            self.env().emit_event(RoundRegistered {
+                round_id,
+                round,
+                publisher,
+            });
+            Ok(round_id)
+        }
+
+        /// Updates the cached status of `round_id`.
+        ///
+        /// Callable by registrars and by the round's publisher.
+        #[ink(message)]
+        pub fn set_status(&mut self, round_id: RoundId, status: RoundStatus) -> Result<(), Error> {
+            let mut entry = self.rounds.get(round_id).ok_or(Error::UnknownRound)?;
+            let caller = self.env().caller();
+            if !self.registrars.contains(caller) && caller != entry.publisher {
+                return Err(Error::NotRegistrar);
+            }
+            entry.status = status;
+            self.rounds.insert(round_id, &entry);
+            self.env().emit_event(StatusUpdated { round_id, status });
+            Ok(())
+        }
+
+        /// Returns the entry registered under `round_id`, if any.
+        #[ink(message)]
+        pub fn get_round(&self, round_id: RoundId) -> Option<RoundEntry> {
+            self.rounds.get(round_id)
+        }
+
+        /// Returns the id of the round deployed at `round`, if registered.
+        #[ink(message)]
+        pub fn round_id_of(&self, round: AccountId) -> Option<RoundId> {
+            self.round_ids.get(round)
+        }
+
+        /// Returns the number of registered rounds.
+        #[ink(message)]
+        pub fn round_count(&self) -> u32 {
+            self.next_round_id
+        }
+
+        /// Lists registered rounds, optionally filtered by status. `offset`
+        /// indexes into the id space and `limit` caps the ids walked, so
+        /// pages stay weight-bounded regardless of how many match.
+        #[ink(message)]
+        pub fn list_rounds(
+            &self,
+            status: Option<RoundStatus>,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(RoundId, RoundEntry)> {
+            let end = offset.saturating_add(limit).min(self.next_round_id);
+            (offset..end)
+                .filter_map(|round_id| {
+                    let entry = self.rounds.get(round_id)?;
+                    match status {
+                        Some(wanted) if entry.status != wanted => None,
+                        _ => Some((round_id, entry)),
+                    }
+                })
+                .collect()
+        }
+
+        /// Returns `account`'s claim history across every registered
+        /// round, oldest first, from `offset` for up to `limit` entries —
+        /// the single on-chain résumé a storage provider can point at.
+        #[ink(message)]
+        pub fn history_of(
+            &self,
+            account: AccountId,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<ClaimHistoryEntry> {
+            let history = self.history.get(account).unwrap_or_default();
+            let total = u32::try_from(history.len()).unwrap_or(u32::MAX);
+            let start = offset.min(total);
+            let end = offset.saturating_add(limit).min(total);
+            history[start as usize..end as usize].to_vec()
+        }
+
+        /// Returns the number of history entries recorded for `account`.
+        #[ink(message)]
+        pub fn history_count(&self, account: AccountId) -> u32 {
+            u32::try_from(self.history.get(account).unwrap_or_default().len()).unwrap_or(u32::MAX)
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            self.ownership
+                .ensure_owner(self.env().caller())
+                .map_err(|_| Error::NotOwner)
+        }
+    }
+
+    impl ClaimHistory for Registry {
+        #[ink(message)]
+        fn note_claim(
+            &mut self,
+            account: AccountId,
+            cid: Vec<u8>,
+            reward: Balance,
+        ) -> Result<(), HistoryError> {
+            let round = self.env().caller();
+            if !self.round_ids.contains(round) {
+                return Err(HistoryError::NotRegistered);
+            }
+            let mut history = self.history.get(account).unwrap_or_default();
+            history.push(ClaimHistoryEntry {
+                round,
+                cid: cid.clone(),
+                block: self.env().block_number(),
+                reward,
+            });
+            self.history.insert(account, &history);
+            self.env().emit_event(ClaimNoted {
+                round,
+                account,
+                cid,
+            });
+            Ok(())
+        }
+    }
+
+    impl Default for Registry {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Ownable for Registry {
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            self.ownership.owner()
+        }
+
+        #[ink(message)]
+        fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError> {
+            let event = self.ownership.transfer(self.env().caller(), new_owner)?;
+            self.env().emit_event(event);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<ink::env::DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
+
+        fn registry() -> Registry {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut registry = Registry::new();
+            registry
+                .add_registrar(accounts.django)
+                .expect("alice owns the registry");
+            registry
+        }
+
+        #[ink::test]
+        fn registration_is_registrar_only_and_deduplicated() {
+            let accounts = accounts();
+            let mut registry = registry();
+            set_caller(accounts.bob);
+            assert_eq!(
+                registry.register_round(accounts.eve, accounts.bob, Vec::new()),
+                Err(Error::NotRegistrar)
+            );
+            set_caller(accounts.django);
+            assert_eq!(
+                registry.register_round(accounts.eve, accounts.bob, Vec::new()),
+                Ok(0)
+            );
+            assert_eq!(
+                registry.register_round(accounts.eve, accounts.bob, Vec::new()),
+                Err(Error::AlreadyRegistered)
+            );
+            assert_eq!(registry.round_id_of(accounts.eve), Some(0));
+            assert_eq!(registry.round_count(), 1);
+        }
+
+        #[ink::test]
+        fn status_updates_accept_registrars_and_publishers() {
+            let accounts = accounts();
+            let mut registry = registry();
+            set_caller(accounts.django);
+            let round_id = registry
+                .register_round(accounts.eve, accounts.bob, Vec::new())
+                .expect("django registers");
+            set_caller(accounts.charlie);
+            assert_eq!(
+                registry.set_status(round_id, RoundStatus::Active),
+                Err(Error::NotRegistrar)
+            );
+            set_caller(accounts.bob);
+            assert!(registry.set_status(round_id, RoundStatus::Active).is_ok());
+            assert_eq!(
+                registry.get_round(round_id).map(|entry| entry.status),
+                Some(RoundStatus::Active)
+            );
+        }
+
+        #[ink::test]
+        fn history_accepts_registered_rounds_only_and_paginates() {
+            let accounts = accounts();
+            let mut registry = registry();
+            set_caller(accounts.django);
+            registry
+                .register_round(accounts.eve, accounts.bob, Vec::new())
+                .expect("django registers");
+
+            // only the registered round address may push
+            set_caller(accounts.charlie);
+            assert_eq!(
+                registry.note_claim(accounts.bob, ink::prelude::vec![1u8], 10),
+                Err(HistoryError::NotRegistered)
+            );
+            set_caller(accounts.eve);
+            assert!(registry
+                .note_claim(accounts.bob, ink::prelude::vec![1u8], 10)
+                .is_ok());
+            assert!(registry
+                .note_claim(accounts.bob, ink::prelude::vec![2u8], 20)
+                .is_ok());
+
+            assert_eq!(registry.history_count(accounts.bob), 2);
+            let page = registry.history_of(accounts.bob, 1, 10);
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].round, accounts.eve);
+            assert_eq!(page[0].cid, ink::prelude::vec![2u8]);
+            assert_eq!(page[0].reward, 20);
+            assert!(registry.history_of(accounts.charlie, 0, 10).is_empty());
+        }
+
+        #[ink::test]
+        fn listing_filters_by_status_and_paginates() {
+            let accounts = accounts();
+            let mut registry = registry();
+            set_caller(accounts.django);
+            for round in [accounts.eve, accounts.frank, accounts.charlie] {
+                registry
+                    .register_round(round, accounts.bob, Vec::new())
+                    .expect("django registers");
+            }
+            registry
+                .set_status(1, RoundStatus::Active)
+                .expect("django updates");
+            let active = registry.list_rounds(Some(RoundStatus::Active), 0, 10);
+            assert_eq!(active.len(), 1);
+            assert_eq!(active[0].0, 1);
+            // offset and limit index the id space, not the filtered result
+            assert_eq!(registry.list_rounds(None, 1, 1).len(), 1);
+            assert_eq!(registry.list_rounds(None, 3, 10), Vec::new());
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! # Claim Router
+//!
+//! Dispatches a batch of fragment claims across many
+//! [`fragments::fragments::FragmentsRound`] contracts in a single
+//! transaction, reporting a per-item outcome, so storage nodes active in
+//! many rounds do not pay per-round transaction overhead.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+pub mod router {
+    use fa_nft::fa_nft::{FragmentCid, TokenId};
+    use fragments::fragments::{Error as RoundError, FragmentsRoundRef};
+    use fragments::mmr::{Leaf, MergeLeaves, Proof};
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::call::FromAccountId;
+    use ink::prelude::vec::Vec;
+
+    /// One claim in a routed batch, addressed to a specific round.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct RoutedClaim {
+        /// The round the claim is submitted to.
+        pub round: AccountId,
+        /// MMR membership proof for the fragment's digest.
+        pub proof: Proof<Leaf, MergeLeaves>,
+        /// Identifier of the claimed fragment.
+        pub cid: FragmentCid,
+        /// The fragment's digest, as committed in the round's MMR.
+        pub hash: Vec<u8>,
+    }
+
+    /// The outcome of one routed claim. A failure never aborts the rest
+    /// of the batch.
+    // the codec derive casts each variant's index `usize as u8` and only
+    // allows the truncation lint on unit variants, so data-carrying
+    // enums have to spell the allow themselves
+    #[allow(clippy::cast_possible_truncation)]
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RouteResult {
+        /// The round accepted the claim and minted this acknowledgement.
+        Minted(TokenId),
+        /// The round rejected the claim with the given error.
+        Rejected(RoundError),
+        /// The round could not be called at all: not a round contract,
+        /// weight allotment exhausted, or the call trapped.
+        Unreachable,
+    }
+
+    /// Emitted after a batch has been dispatched, with its tally.
+    #[ink(event)]
+    pub struct BatchRouted {
+        #[ink(topic)]
+        submitter: AccountId,
+        accepted: u32,
+        rejected: u32,
+    }
+
+    /// The router holds no state of its own; rounds remain the source of
+    /// truth for every claim.
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Router {}
+
+    impl Router {
+        /// Weight granted to each routed claim — proof verification plus
+        /// the round's own acknowledgement mint — so one heavy item
+        /// cannot starve the rest of the batch.
+        const CLAIM_REF_TIME_LIMIT: u64 = 10_000_000_000;
+        /// Proof-size budget for each routed claim.
+        const CLAIM_PROOF_SIZE_LIMIT: u64 = 256 * 1024;
+
+        /// Creates a router.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Dispatches `claims` to their rounds in order, crediting every
+        /// accepted claim to the caller, and returns one result per item.
+        /// Each item is dispatched under its own weight budget and a
+        /// failing item leaves the rest of the batch untouched.
+        #[ink(message)]
+        pub fn route_claims(&mut self, claims: Vec<RoutedClaim>) -> Vec<RouteResult> {
+            let caller = self.env().caller();
+            let mut results = Vec::with_capacity(claims.len());
+            let mut accepted = 0u32;
+            for claim in claims {
+                let mut round = FragmentsRoundRef::from_account_id(claim.round);
+                let outcome = round
+                    .call_mut()
+                    .claim_fragment(claim.proof, claim.cid, claim.hash, Some(caller), None, None)
+                    .ref_time_limit(Self::CLAIM_REF_TIME_LIMIT)
+                    .proof_size_limit(Self::CLAIM_PROOF_SIZE_LIMIT)
+                    .try_invoke();
+                let result = match outcome {
+                    Ok(Ok(Ok(token_id))) => {
+                        accepted = accepted.saturating_add(1);
+                        RouteResult::Minted(token_id)
+                    }
+                    Ok(Ok(Err(error))) => RouteResult::Rejected(error),
+                    _ => RouteResult::Unreachable,
+                };
+                results.push(result);
+            }
+            self.env().emit_event(BatchRouted {
+                submitter: caller,
+                accepted,
+                rejected: u32::try_from(results.len())
+                    .unwrap_or(u32::MAX)
+                    .saturating_sub(accepted),
+            });
+            results
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn empty_batches_route_to_nothing() {
+            let mut router = Router::new();
+            assert_eq!(router.route_claims(Vec::new()), Vec::new());
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! The standard interface for querying fragment acknowledgement data, so
+//! reputation, governance, and reward-strategy contracts can read
+//! acknowledgements from any compliant NFT contract, not just the bundled
+//! `FaNft`.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use fragments_types::{BlockNumber, FragmentAcknowledgement, FragmentCid, TokenId};
+
+/// Read access to a collection's acknowledgement data.
+#[ink::trait_definition]
+pub trait Acknowledgeable {
+    /// Returns the acknowledgement record minted with the token, if the
+    /// token exists.
+    #[ink(message)]
+    fn get_fragment_acknowledgment(&self, id: TokenId) -> Option<FragmentAcknowledgement>;
+
+    /// Returns the number of live acknowledgements of fragment `cid`.
+    #[ink(message)]
+    fn acknowledgment_count(&self, cid: FragmentCid) -> u32;
+
+    /// Returns `true` if fragment `cid` has at least one live
+    /// acknowledgement.
+    #[ink(message)]
+    fn is_acknowledged(&self, cid: FragmentCid) -> bool;
+}
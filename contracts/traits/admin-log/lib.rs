@@ -0,0 +1,181 @@
+//! An on-chain audit log of privileged activity, shared by the fragments
+//! contracts: every owner or guardian message records who called what,
+//! when, and with which key arguments into a bounded ring buffer, so
+//! operators and auditors can review admin history from chain state
+//! alone instead of trusting an external indexer to have caught every
+//! event.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Block number type entries are stamped with (the default
+/// environment's).
+pub type BlockNumber = u32;
+
+/// Entries retained per contract. Old entries are overwritten oldest
+/// first; auditors wanting full history follow the events instead.
+pub const ADMIN_LOG_CAPACITY: u32 = 64;
+
+/// One privileged call, as recorded by the host contract.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct AdminLogEntry {
+    /// The account that made the call.
+    pub caller: AccountId,
+    /// Name of the message that was called, as UTF-8 bytes.
+    pub message: Vec<u8>,
+    /// The block at which the call was made.
+    pub block: BlockNumber,
+    /// The call's key arguments, SCALE-encoded by the host contract in
+    /// the message's own parameter shapes.
+    pub args: Vec<u8>,
+}
+
+/// One page of a contract's admin log, oldest entry first.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AdminLogPage {
+    /// The entries of this page.
+    pub entries: Vec<AdminLogEntry>,
+    /// The offset to continue from, or `None` when the retained log has
+    /// been fully walked.
+    pub next_offset: Option<u32>,
+}
+
+/// Reusable ring-buffer storage for contracts keeping an admin log.
+///
+/// Embed this in the contract's storage struct, call
+/// [`AdminLogData::record`] from every privileged message, and expose
+/// [`AdminLogData::page`] as an `admin_log(offset, limit)` query.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct AdminLogData {
+    /// The retained entries; wraps around once capacity is reached.
+    entries: Vec<AdminLogEntry>,
+    /// Index of the oldest retained entry, once the buffer has wrapped.
+    start: u32,
+}
+
+impl AdminLogData {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            start: 0,
+        }
+    }
+
+    /// Records one privileged call, evicting the oldest entry once the
+    /// buffer is full.
+    pub fn record(
+        &mut self,
+        caller: AccountId,
+        message: &[u8],
+        block: BlockNumber,
+        args: Vec<u8>,
+    ) {
+        let entry = AdminLogEntry {
+            caller,
+            message: message.to_vec(),
+            block,
+            args,
+        };
+        if self.entries.len() < ADMIN_LOG_CAPACITY as usize {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.start as usize] = entry;
+            self.start = self
+                .start
+                .saturating_add(1)
+                .checked_rem(ADMIN_LOG_CAPACITY)
+                .unwrap_or(0);
+        }
+    }
+
+    /// Returns the number of retained entries.
+    pub fn len(&self) -> u32 {
+        // the buffer never outgrows ADMIN_LOG_CAPACITY
+        u32::try_from(self.entries.len()).unwrap_or(ADMIN_LOG_CAPACITY)
+    }
+
+    /// Returns whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Walks the retained log oldest first, from `offset` for up to
+    /// `limit` entries.
+    pub fn page(&self, offset: u32, limit: u32) -> AdminLogPage {
+        let total = self.len();
+        let end = offset.saturating_add(limit).min(total);
+        let mut entries = Vec::new();
+        for position in offset..end {
+            let index = self
+                .start
+                .saturating_add(position)
+                .checked_rem(total)
+                .unwrap_or(0);
+            entries.push(self.entries[index as usize].clone());
+        }
+        AdminLogPage {
+            entries,
+            next_offset: (end < total).then_some(end),
+        }
+    }
+}
+
+impl Default for AdminLogData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    #[test]
+    fn pages_walk_the_log_oldest_first() {
+        let mut log = AdminLogData::new();
+        for n in 0..3u8 {
+            log.record(account(n), b"set_treasury", n as BlockNumber, vec![n]);
+        }
+        let page = log.page(0, 2);
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.entries[0].args, vec![0]);
+        assert_eq!(page.next_offset, Some(2));
+        let page = log.page(2, 2);
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.entries[0].args, vec![2]);
+        assert_eq!(page.next_offset, None);
+    }
+
+    #[test]
+    fn the_buffer_wraps_evicting_the_oldest_entries() {
+        let mut log = AdminLogData::new();
+        for n in 0..ADMIN_LOG_CAPACITY + 2 {
+            log.record(account(1), b"activate", n, n.to_le_bytes().to_vec());
+        }
+        assert_eq!(log.len(), ADMIN_LOG_CAPACITY);
+        let page = log.page(0, 1);
+        // entries 0 and 1 were evicted; the oldest retained entry is 2
+        assert_eq!(page.entries[0].args, 2u32.to_le_bytes().to_vec());
+        let last = log.page(log.len() - 1, 1);
+        assert_eq!(
+            last.entries[0].args,
+            (ADMIN_LOG_CAPACITY + 1).to_le_bytes().to_vec()
+        );
+        assert_eq!(last.next_offset, None);
+    }
+}
@@ -0,0 +1,21 @@
+//! The burning interface for acknowledgement NFT contracts, so workspace
+//! contracts (staking, marketplace, rounds with spend-on-payout semantics)
+//! can be written against a trait instead of the concrete `FaNft`.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+pub use fragments_types::{BurnError, TokenId};
+
+/// Cross-contract burning of fragment acknowledgement tokens.
+#[ink::trait_definition]
+pub trait Burnable {
+    /// Burns token `id` owned by the caller.
+    #[ink(message)]
+    fn burn(&mut self, id: TokenId) -> Result<(), BurnError>;
+
+    /// Burns token `id` held by `from`, if the caller is the owner, approved
+    /// for the token, or an approved operator.
+    #[ink(message)]
+    fn burn_from(&mut self, from: AccountId, id: TokenId) -> Result<(), BurnError>;
+}
@@ -0,0 +1,37 @@
+//! The interface a claim-history sink (normally the round registry)
+//! exposes so rounds can push accepted claims to it at claim time,
+//! building a cross-round résumé per account.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Balance type rewards are denominated in (the default environment's).
+pub type Balance = u128;
+
+/// Errors a claim-history sink may return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum HistoryError {
+    /// The calling contract is not registered with the sink.
+    NotRegistered,
+}
+
+/// A sink accumulating accepted claims across rounds.
+///
+/// A round configured with a sink calls [`ClaimHistory::note_claim`]
+/// after each accepted claim; the sink records which round pushed it
+/// from the caller address, so entries cannot be forged on behalf of
+/// another round.
+#[ink::trait_definition]
+pub trait ClaimHistory {
+    /// Records that the calling round accepted a claim of `cid` by
+    /// `account`, with the immediate reward accrual the claim caused.
+    #[ink(message)]
+    fn note_claim(
+        &mut self,
+        account: AccountId,
+        cid: Vec<u8>,
+        reward: Balance,
+    ) -> Result<(), HistoryError>;
+}
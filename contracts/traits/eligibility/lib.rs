@@ -0,0 +1,18 @@
+//! The interface an eligibility verifier contract must expose so a
+//! `FragmentsRound` can gate claims on it.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+/// A pluggable claim-eligibility policy.
+///
+/// A round configured with a verifier calls [`Eligibility::is_eligible`]
+/// before accepting a claim, letting deployments enforce KYC, allowlists
+/// or membership requirements without baking policy into the round
+/// contract.
+#[ink::trait_definition]
+pub trait Eligibility {
+    /// Returns `true` if `account` may claim fragments.
+    #[ink(message)]
+    fn is_eligible(&self, account: AccountId) -> bool;
+}
@@ -0,0 +1,25 @@
+//! The enumeration interface for acknowledgement NFT contracts, in the
+//! style of ERC721Enumerable, so indexers and other workspace contracts can
+//! walk a collection without depending on the concrete `FaNft`.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+pub use fragments_types::TokenId;
+
+/// Enumeration over all live tokens of a collection.
+#[ink::trait_definition]
+pub trait Enumerable {
+    /// Returns the number of live (minted and not burned) tokens.
+    #[ink(message)]
+    fn total_supply(&self) -> u32;
+
+    /// Returns the token at `index` in the global enumeration, if any.
+    /// Ordering is unspecified and may change on burns.
+    #[ink(message)]
+    fn token_by_index(&self, index: u32) -> Option<TokenId>;
+
+    /// Returns the token at `index` among those owned by `owner`, if any.
+    #[ink(message)]
+    fn token_of_owner_by_index(&self, owner: AccountId, index: u32) -> Option<TokenId>;
+}
@@ -0,0 +1,127 @@
+//! A guardian interface shared by the fragments contracts: a dedicated
+//! account limited to operational powers (pausing, audits), so day-to-day
+//! incident response does not require handling the owner key.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+/// Errors raised by guardian checks and appointments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum GuardianError {
+    /// The caller is not the contract owner, who alone appoints guardians.
+    NotOwner,
+    /// The caller is not the current guardian.
+    NotGuardian,
+}
+
+/// Emitted when the guardian of a contract is appointed or cleared.
+#[ink::event]
+pub struct GuardianChanged {
+    pub guardian: Option<AccountId>,
+}
+
+/// A dedicated operational role beside the owner.
+///
+/// Implementors are expected to let the guardian exercise pause and audit
+/// powers only — never funds movement or configuration — and to let the
+/// owner appoint or clear the role, emitting [`GuardianChanged`] when they
+/// do.
+#[ink::trait_definition]
+pub trait Guarded {
+    /// Returns the current guardian, if one is appointed.
+    #[ink(message)]
+    fn guardian(&self) -> Option<AccountId>;
+
+    /// Appoints `guardian` as the contract's guardian, or clears the role
+    /// with `None`.
+    ///
+    /// Only callable by the contract owner.
+    #[ink(message)]
+    fn set_guardian(&mut self, guardian: Option<AccountId>) -> Result<(), GuardianError>;
+}
+
+/// Reusable guardian storage for contracts implementing [`Guarded`].
+///
+/// Embed this in the contract's storage struct and delegate the trait
+/// messages to it, so every contract enforces identical semantics. The
+/// owner check guarding [`GuardianData::set`] stays with the contract,
+/// which already owns that notion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct GuardianData {
+    guardian: Option<AccountId>,
+}
+
+impl GuardianData {
+    /// Creates guardian data with no guardian appointed.
+    pub fn new() -> Self {
+        Self { guardian: None }
+    }
+
+    /// Returns the current guardian, if any.
+    pub fn guardian(&self) -> Option<AccountId> {
+        self.guardian
+    }
+
+    /// Returns `Ok(())` if `caller` is the current guardian.
+    pub fn ensure_guardian(&self, caller: AccountId) -> Result<(), GuardianError> {
+        if self.guardian != Some(caller) {
+            return Err(GuardianError::NotGuardian);
+        }
+        Ok(())
+    }
+
+    /// Appoints or clears the guardian, returning the event the contract
+    /// should emit.
+    pub fn set(&mut self, guardian: Option<AccountId>) -> GuardianChanged {
+        self.guardian = guardian;
+        GuardianChanged { guardian }
+    }
+}
+
+impl Default for GuardianData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    #[test]
+    fn ensure_guardian_accepts_only_the_appointed_account() {
+        let mut guardian = GuardianData::new();
+        assert_eq!(
+            guardian.ensure_guardian(account(1)),
+            Err(GuardianError::NotGuardian)
+        );
+        guardian.set(Some(account(1)));
+        assert_eq!(guardian.ensure_guardian(account(1)), Ok(()));
+        assert_eq!(
+            guardian.ensure_guardian(account(2)),
+            Err(GuardianError::NotGuardian)
+        );
+    }
+
+    #[test]
+    fn clearing_the_role_revokes_the_previous_guardian() {
+        let mut guardian = GuardianData::new();
+        guardian.set(Some(account(1)));
+        let event = guardian.set(None);
+        assert_eq!(event.guardian, None);
+        assert_eq!(guardian.guardian(), None);
+        assert_eq!(
+            guardian.ensure_guardian(account(1)),
+            Err(GuardianError::NotGuardian)
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! The minting interface an acknowledgement NFT contract must expose so a
+//! `FragmentsRound` can mint through any compliant implementation rather
+//! than the bundled `FaNft` specifically.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+pub use fragments_types::{FragmentCid, MintError, TokenId};
+
+/// Cross-contract minting of fragment acknowledgement tokens.
+///
+/// The message signature — and therefore its selector — is exactly the one
+/// `FaNft` exposes, so any contract implementing this trait can be linked
+/// to a round in its place.
+#[ink::trait_definition]
+pub trait Mintable {
+    /// Mints an acknowledgement token for fragment `cid` to `to`, recording
+    /// the fragment's rarity `tier`, and returns the new token's id.
+    #[ink(message)]
+    fn mint(&mut self, to: AccountId, cid: FragmentCid, tier: u8) -> Result<TokenId, MintError>;
+
+    /// Like [`Mintable::mint`], additionally recording the beacon round of
+    /// a pulse the minting round has verified, as the acknowledgement's
+    /// externally checkable wall-clock anchor.
+    #[ink(message)]
+    fn mint_anchored(
+        &mut self,
+        to: AccountId,
+        cid: FragmentCid,
+        tier: u8,
+        beacon_round: Option<u64>,
+    ) -> Result<TokenId, MintError>;
+}
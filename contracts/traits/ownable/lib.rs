@@ -0,0 +1,194 @@
+//! A minimal ownership interface shared by the fragments contracts, plus a
+//! reusable storage struct and event so implementors do not re-invent the
+//! same checks with divergent failure behavior.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+/// Errors raised by ownership checks and transfers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum OwnableError {
+    /// The caller is not the current owner.
+    NotOwner,
+    /// The caller is not the proposed pending owner.
+    NotPendingOwner,
+}
+
+/// Emitted when ownership of a contract changes hands.
+#[ink::event]
+pub struct OwnershipTransferred {
+    #[ink(topic)]
+    pub previous_owner: AccountId,
+    #[ink(topic)]
+    pub new_owner: AccountId,
+}
+
+/// Single-account ownership of a contract.
+///
+/// Implementors are expected to restrict administrative messages to the
+/// current owner and to allow the owner to hand the role to another account,
+/// emitting [`OwnershipTransferred`] when they do.
+#[ink::trait_definition]
+pub trait Ownable {
+    /// Returns the current owner of the contract.
+    #[ink(message)]
+    fn owner(&self) -> AccountId;
+
+    /// Transfers ownership of the contract to `new_owner`.
+    ///
+    /// Only callable by the current owner.
+    #[ink(message)]
+    fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), OwnableError>;
+}
+
+/// Reusable ownership storage for contracts implementing [`Ownable`].
+///
+/// Embed this in the contract's storage struct and delegate the trait
+/// messages to it, so every contract enforces identical semantics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct OwnershipData {
+    owner: AccountId,
+    pending_owner: Option<AccountId>,
+}
+
+impl OwnershipData {
+    /// Creates ownership data with `owner` as the initial owner.
+    pub fn new(owner: AccountId) -> Self {
+        Self {
+            owner,
+            pending_owner: None,
+        }
+    }
+
+    /// Returns the current owner.
+    pub fn owner(&self) -> AccountId {
+        self.owner
+    }
+
+    /// Returns `Ok(())` if `caller` is the current owner.
+    pub fn ensure_owner(&self, caller: AccountId) -> Result<(), OwnableError> {
+        if caller != self.owner {
+            return Err(OwnableError::NotOwner);
+        }
+        Ok(())
+    }
+
+    /// Transfers ownership to `new_owner` if `caller` is the current owner,
+    /// returning the event the contract should emit. Any pending two-step
+    /// proposal is discarded.
+    pub fn transfer(
+        &mut self,
+        caller: AccountId,
+        new_owner: AccountId,
+    ) -> Result<OwnershipTransferred, OwnableError> {
+        self.ensure_owner(caller)?;
+        let previous_owner = self.owner;
+        self.owner = new_owner;
+        self.pending_owner = None;
+        Ok(OwnershipTransferred {
+            previous_owner,
+            new_owner,
+        })
+    }
+
+    /// Proposes `new_owner` as the pending owner, to take effect once they
+    /// accept. Only the current owner may propose.
+    pub fn propose(
+        &mut self,
+        caller: AccountId,
+        new_owner: AccountId,
+    ) -> Result<(), OwnableError> {
+        self.ensure_owner(caller)?;
+        self.pending_owner = Some(new_owner);
+        Ok(())
+    }
+
+    /// Returns the proposed pending owner, if any.
+    pub fn pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner
+    }
+
+    /// Completes a two-step transfer: `caller` must be the pending owner.
+    /// Returns the event the contract should emit.
+    pub fn accept(&mut self, caller: AccountId) -> Result<OwnershipTransferred, OwnableError> {
+        if self.pending_owner != Some(caller) {
+            return Err(OwnableError::NotPendingOwner);
+        }
+        let previous_owner = self.owner;
+        self.owner = caller;
+        self.pending_owner = None;
+        Ok(OwnershipTransferred {
+            previous_owner,
+            new_owner: caller,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(byte: u8) -> AccountId {
+        AccountId::from([byte; 32])
+    }
+
+    #[test]
+    fn ensure_owner_accepts_only_the_owner() {
+        let ownership = OwnershipData::new(account(1));
+        assert_eq!(ownership.ensure_owner(account(1)), Ok(()));
+        assert_eq!(ownership.ensure_owner(account(2)), Err(OwnableError::NotOwner));
+    }
+
+    #[test]
+    fn transfer_requires_owner_and_reports_both_parties() {
+        let mut ownership = OwnershipData::new(account(1));
+        assert_eq!(
+            ownership.transfer(account(2), account(3)).map(|_| ()),
+            Err(OwnableError::NotOwner)
+        );
+        let event = ownership.transfer(account(1), account(3)).expect("owner may transfer");
+        assert_eq!(event.previous_owner, account(1));
+        assert_eq!(event.new_owner, account(3));
+        assert_eq!(ownership.owner(), account(3));
+        assert_eq!(ownership.ensure_owner(account(1)), Err(OwnableError::NotOwner));
+    }
+
+    #[test]
+    fn two_step_transfer_requires_acceptance_by_the_proposed_owner() {
+        let mut ownership = OwnershipData::new(account(1));
+        assert_eq!(
+            ownership.propose(account(2), account(2)),
+            Err(OwnableError::NotOwner)
+        );
+        assert_eq!(ownership.propose(account(1), account(2)), Ok(()));
+        assert_eq!(ownership.pending_owner(), Some(account(2)));
+        // ownership does not move until the proposal is accepted
+        assert_eq!(ownership.owner(), account(1));
+        assert_eq!(
+            ownership.accept(account(3)).map(|_| ()),
+            Err(OwnableError::NotPendingOwner)
+        );
+        let event = ownership.accept(account(2)).expect("pending owner accepts");
+        assert_eq!(event.previous_owner, account(1));
+        assert_eq!(event.new_owner, account(2));
+        assert_eq!(ownership.owner(), account(2));
+        assert_eq!(ownership.pending_owner(), None);
+    }
+
+    #[test]
+    fn direct_transfer_discards_pending_proposal() {
+        let mut ownership = OwnershipData::new(account(1));
+        ownership.propose(account(1), account(2)).unwrap();
+        ownership.transfer(account(1), account(3)).unwrap();
+        assert_eq!(ownership.pending_owner(), None);
+        assert_eq!(
+            ownership.accept(account(2)).map(|_| ()),
+            Err(OwnableError::NotPendingOwner)
+        );
+    }
+}
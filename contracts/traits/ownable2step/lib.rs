@@ -0,0 +1,29 @@
+//! A two-step ownership-transfer interface: the current owner proposes a
+//! successor, and ownership only moves once the successor accepts. This
+//! protects against handing a contract to a mistyped or unreachable
+//! address.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+use ownable::OwnableError;
+
+/// Two-step ownership transfer, complementing [`ownable::Ownable`].
+#[ink::trait_definition]
+pub trait Ownable2Step {
+    /// Proposes `new_owner` as the next owner. The proposal replaces any
+    /// previous one and takes effect only on acceptance.
+    ///
+    /// Only callable by the current owner.
+    #[ink(message)]
+    fn propose_owner(&mut self, new_owner: AccountId) -> Result<(), OwnableError>;
+
+    /// Returns the currently proposed owner, if any.
+    #[ink(message)]
+    fn pending_owner(&self) -> Option<AccountId>;
+
+    /// Accepts a pending proposal, making the caller the new owner.
+    ///
+    /// Only callable by the proposed pending owner.
+    #[ink(message)]
+    fn accept_ownership(&mut self) -> Result<(), OwnableError>;
+}
@@ -0,0 +1,20 @@
+//! The interface a reward strategy contract must expose so a
+//! `FragmentsRound` can delegate its payout formula to it.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+pub use fragments_types::{Balance, FragmentCid};
+
+/// A pluggable reward formula.
+///
+/// A round configured with a strategy calls [`RewardStrategy::compute_reward`]
+/// instead of its built-in per-claim formula, passing the claimer and the
+/// cids of every fragment that account has claimed in the round.
+#[ink::trait_definition]
+pub trait RewardStrategy {
+    /// Returns the total reward owed to `claimer` for `claims_data`.
+    #[ink(message)]
+    fn compute_reward(&self, claimer: AccountId, claims_data: Vec<FragmentCid>) -> Balance;
+}
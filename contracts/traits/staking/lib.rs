@@ -0,0 +1,17 @@
+//! The interface a staking contract must expose so other contracts can
+//! check an account's stake, e.g. a `FragmentsRound` gating claims on a
+//! minimum staked amount.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+/// Balance type stakes are denominated in (the default environment's).
+pub type Balance = u128;
+
+/// Read access to an account's staked funds.
+#[ink::trait_definition]
+pub trait Staking {
+    /// Returns the amount currently staked by `account`.
+    #[ink(message)]
+    fn staked_balance(&self, account: AccountId) -> Balance;
+}
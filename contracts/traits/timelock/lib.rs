@@ -0,0 +1,179 @@
+//! A timelock queue shared by the fragments contracts: sensitive owner
+//! operations are scheduled, become executable only after a configured
+//! number of blocks, and can be cancelled in between, so token holders
+//! and watchers get advance notice of privileged changes instead of
+//! finding out after the fact.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::vec::Vec;
+
+/// Block number type delays are expressed in (the default environment's).
+pub type BlockNumber = u32;
+
+/// Identifier of a scheduled action, unique per contract for its
+/// lifetime so cancelled and executed ids are never reused.
+pub type ActionId = u64;
+
+/// Errors raised by the timelock queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TimelockError {
+    /// No scheduled action exists under the given id.
+    UnknownAction,
+    /// The action's delay has not elapsed yet.
+    NotReady,
+}
+
+/// An action sitting in the queue, waiting out its delay.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Scheduled<A> {
+    /// Identifier to execute or cancel the action by.
+    pub id: ActionId,
+    /// The scheduled action, in the host contract's own action type.
+    pub action: A,
+    /// The first block at which the action may be executed.
+    pub executable_at: BlockNumber,
+}
+
+/// Reusable timelock storage for contracts queueing admin actions.
+///
+/// Embed this in the contract's storage struct with the contract's own
+/// action enum as `A`. A delay of zero — the default — disables the
+/// timelock entirely: the host contract is expected to keep its direct
+/// admin messages working in that case and refuse them once a delay is
+/// configured, so enabling the timelock is a deliberate, observable act.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct TimelockData<A> {
+    /// Blocks between scheduling and executability; zero disables the
+    /// timelock.
+    delay: BlockNumber,
+    /// The id the next scheduled action receives.
+    next_id: ActionId,
+    /// Actions scheduled and neither executed nor cancelled, in
+    /// scheduling order. Expected to stay short: admin operations are
+    /// rare and executed entries are removed.
+    queue: Vec<Scheduled<A>>,
+}
+
+impl<A> TimelockData<A> {
+    /// Creates an empty queue with the timelock disabled.
+    pub fn new() -> Self {
+        Self {
+            delay: 0,
+            next_id: 0,
+            queue: Vec::new(),
+        }
+    }
+
+    /// Returns the configured delay in blocks, zero when disabled.
+    pub fn delay(&self) -> BlockNumber {
+        self.delay
+    }
+
+    /// Sets the delay. Already-scheduled actions keep the executability
+    /// block they were scheduled with.
+    pub fn set_delay(&mut self, delay: BlockNumber) {
+        self.delay = delay;
+    }
+
+    /// Returns whether the timelock is enabled, i.e. whether direct
+    /// admin messages must be refused in favor of the queue.
+    pub fn required(&self) -> bool {
+        self.delay > 0
+    }
+
+    /// Queues `action` as of block `now`, returning its id and the block
+    /// it becomes executable at.
+    pub fn schedule(&mut self, action: A, now: BlockNumber) -> (ActionId, BlockNumber) {
+        let id = self.next_id;
+        self.next_id = self.next_id.saturating_add(1);
+        let executable_at = now.saturating_add(self.delay);
+        self.queue.push(Scheduled {
+            id,
+            action,
+            executable_at,
+        });
+        (id, executable_at)
+    }
+
+    /// Removes the scheduled action `id` from the queue without
+    /// executing it.
+    pub fn cancel(&mut self, id: ActionId) -> Result<Scheduled<A>, TimelockError> {
+        let position = self
+            .queue
+            .iter()
+            .position(|scheduled| scheduled.id == id)
+            .ok_or(TimelockError::UnknownAction)?;
+        Ok(self.queue.remove(position))
+    }
+
+    /// Removes and returns the action `id` once its delay has elapsed as
+    /// of block `now`. A not-yet-ready action stays queued.
+    pub fn take_ready(&mut self, id: ActionId, now: BlockNumber) -> Result<A, TimelockError> {
+        let position = self
+            .queue
+            .iter()
+            .position(|scheduled| scheduled.id == id)
+            .ok_or(TimelockError::UnknownAction)?;
+        if now < self.queue[position].executable_at {
+            return Err(TimelockError::NotReady);
+        }
+        Ok(self.queue.remove(position).action)
+    }
+
+    /// Returns every action scheduled and neither executed nor
+    /// cancelled, in scheduling order.
+    pub fn pending(&self) -> &[Scheduled<A>] {
+        &self.queue
+    }
+}
+
+impl<A> Default for TimelockData<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions_wait_out_their_delay() {
+        let mut timelock = TimelockData::new();
+        assert!(!timelock.required());
+        timelock.set_delay(10);
+        assert!(timelock.required());
+        let (id, executable_at) = timelock.schedule("rotate", 5);
+        assert_eq!(executable_at, 15);
+        assert_eq!(timelock.take_ready(id, 14), Err(TimelockError::NotReady));
+        assert_eq!(timelock.pending().len(), 1);
+        assert_eq!(timelock.take_ready(id, 15), Ok("rotate"));
+        assert!(timelock.pending().is_empty());
+        assert_eq!(timelock.take_ready(id, 15), Err(TimelockError::UnknownAction));
+    }
+
+    #[test]
+    fn cancelled_actions_cannot_be_executed_and_ids_are_not_reused() {
+        let mut timelock = TimelockData::new();
+        timelock.set_delay(1);
+        let (first, _) = timelock.schedule("a", 0);
+        let cancelled = timelock.cancel(first).expect("the action is queued");
+        assert_eq!(cancelled.action, "a");
+        assert_eq!(timelock.cancel(first), Err(TimelockError::UnknownAction));
+        assert_eq!(
+            timelock.take_ready(first, 100),
+            Err(TimelockError::UnknownAction)
+        );
+        let (second, _) = timelock.schedule("b", 0);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,27 @@
+//! The interface a listener contract must expose to be notified of
+//! `FaNft` token movements.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+
+pub use fragments_types::TokenId;
+
+/// A token-movement listener.
+///
+/// A collection with registered hooks calls
+/// [`TransferHook::on_token_transfer`] after every mint (`from` is
+/// `None`), transfer, and burn (`to` is `None`), so staking, reputation
+/// and registry contracts can stay in sync without polling. Hook calls
+/// are weight-bounded and best-effort: a failing hook must not block the
+/// movement itself.
+#[ink::trait_definition]
+pub trait TransferHook {
+    /// Notifies the listener that token `id` moved from `from` to `to`.
+    #[ink(message)]
+    fn on_token_transfer(
+        &mut self,
+        from: Option<AccountId>,
+        to: Option<AccountId>,
+        id: TokenId,
+    );
+}
@@ -0,0 +1,89 @@
+//! Shared protocol-treasury and fee-accounting subsystem.
+//!
+//! Fee-bearing contracts (the round, the factory, a future marketplace)
+//! embed a [`TreasuryData`] in their storage: every fee credits a claimable
+//! treasury balance tagged with its [`FeeSource`], and the host contract
+//! exposes `treasury_balance()` / `withdraw_treasury()` messages that drain
+//! the accrued amount to the treasury address. Lifetime per-source totals
+//! are kept for breakdown queries.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::primitives::AccountId;
+use ink::storage::Mapping;
+
+/// Balance type fees are accounted in (the default environment's).
+pub type Balance = u128;
+
+/// The action class a fee was charged for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum FeeSource {
+    /// Deploying a round through the factory.
+    RoundCreation,
+    /// Publishing fragments into a round.
+    Publishing,
+    /// Claim-path fees.
+    Claims,
+    /// Token transfer fees.
+    Transfers,
+    /// Marketplace commissions.
+    Marketplace,
+}
+
+/// The treasury accounting embedded in a fee-bearing contract's storage.
+#[ink::storage_item]
+#[derive(Debug)]
+pub struct TreasuryData {
+    /// The address accrued fees are withdrawn to.
+    treasury: AccountId,
+    /// Fees accrued and not yet withdrawn.
+    claimable: Balance,
+    /// Lifetime fee total per source, for breakdown queries.
+    per_source: Mapping<FeeSource, Balance>,
+}
+
+impl TreasuryData {
+    /// Creates an empty account book draining to `treasury`.
+    pub fn new(treasury: AccountId) -> Self {
+        Self {
+            treasury,
+            claimable: 0,
+            per_source: Mapping::default(),
+        }
+    }
+
+    /// Returns the treasury address.
+    pub fn treasury(&self) -> AccountId {
+        self.treasury
+    }
+
+    /// Points future withdrawals at a new treasury address.
+    pub fn set_treasury(&mut self, treasury: AccountId) {
+        self.treasury = treasury;
+    }
+
+    /// Credits a fee of `amount` charged for `source`.
+    pub fn record_fee(&mut self, source: FeeSource, amount: Balance) {
+        self.claimable = self.claimable.saturating_add(amount);
+        let total = self.per_source.get(source).unwrap_or(0);
+        self.per_source.insert(source, &total.saturating_add(amount));
+    }
+
+    /// Returns the accrued, not yet withdrawn fee balance.
+    pub fn balance(&self) -> Balance {
+        self.claimable
+    }
+
+    /// Returns the lifetime fee total charged for `source`.
+    pub fn of_source(&self, source: FeeSource) -> Balance {
+        self.per_source.get(source).unwrap_or(0)
+    }
+
+    /// Marks the claimable balance as withdrawn, returning the recipient
+    /// and amount the host contract must transfer.
+    pub fn withdraw(&mut self) -> (AccountId, Balance) {
+        let amount = self.claimable;
+        self.claimable = 0;
+        (self.treasury, amount)
+    }
+}
@@ -0,0 +1,226 @@
+//! The data types whose SCALE encodings cross contract boundaries:
+//! fragments and their rarity tiers, acknowledgement records, the MMR
+//! commitment primitives, and the error enums returned across calls.
+//!
+//! `FragmentsRound`, `FaNft`, the factory, and the off-chain tooling all
+//! encode and decode these shapes when talking to each other, so they
+//! live in one crate instead of being mirrored per contract — a field
+//! added on one side of a cross-contract call cannot silently desync
+//! the other.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub mod mmr;
+pub mod smt;
+
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Identifier of a fragment's content: raw multihash/CIDv1 bytes,
+/// bounded by the consuming contract. Legacy numeric cids from earlier
+/// deployments migrate as their little-endian byte encoding.
+pub type FragmentCid = Vec<u8>;
+
+/// Unique identifier of an acknowledgement token.
+pub type TokenId = u32;
+
+/// Block number type fragments and acknowledgements are stamped with
+/// (the default environment's).
+pub type BlockNumber = u32;
+
+/// Balance type reward weights are expressed in (the default
+/// environment's).
+pub type Balance = u128;
+
+/// Rarity tier of a fragment, declared by the round publisher. Tiers
+/// weight the built-in reward formula and are recorded in the minted
+/// acknowledgement's attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Tier {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+impl Tier {
+    /// The multiplier this tier applies to the per-claim reward.
+    pub fn weight(&self) -> Balance {
+        match self {
+            Tier::Common => 1,
+            Tier::Uncommon => 2,
+            Tier::Rare => 4,
+        }
+    }
+}
+
+/// A fragment of data registered in a round, identified by its content
+/// id and committed at a fixed leaf position in the round's MMR.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Fragment {
+    /// Identifier of the fragment's content.
+    pub cid: FragmentCid,
+    /// Position of the fragment's digest in the round MMR.
+    pub leaf_pos: u64,
+    /// The first block at which the fragment may be claimed.
+    pub release_block: BlockNumber,
+    /// Rarity tier of the fragment.
+    pub tier: Tier,
+    /// Size of the fragment in bytes, committed in its MMR leaf and
+    /// used to weight rewards by storage burden. Zero for fragments
+    /// migrated from rounds that did not record sizes.
+    pub size: u64,
+    /// The last block at which the fragment may be claimed, when the
+    /// publisher set one; `None` keeps it claimable for the round's
+    /// whole lifetime.
+    pub claim_deadline: Option<BlockNumber>,
+}
+
+/// The record attached to each acknowledgement token at mint time,
+/// binding a fragment to the account that proved possession of it.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct FragmentAcknowledgement {
+    /// The fragment this acknowledgement refers to.
+    pub cid: FragmentCid,
+    /// The account whose possession proof was accepted.
+    pub claimer: AccountId,
+    /// The block at which the acknowledgement was minted.
+    pub block: BlockNumber,
+    /// Rarity tier of the fragment, as declared by the minting round.
+    pub tier: u8,
+    /// Beacon round of the verified pulse submitted with the claim, if
+    /// one was: an externally checkable wall-clock anchor beside the
+    /// chain-local `block`.
+    pub beacon_round: Option<u64>,
+}
+
+/// Display metadata a round publisher can attach to a fragment so UI
+/// fragment lists are human-readable. Stored per cid by the round and
+/// mirrored into the acknowledgement collection, where each minted
+/// token resolves it through its fragment cid. Field lengths are
+/// bounded by the storing contracts.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct FragmentMeta {
+    /// Short human-readable title.
+    pub title: Vec<u8>,
+    /// Longer free-form description.
+    pub description: Vec<u8>,
+    /// Content-type hint for renderers, e.g. `application/octet-stream`.
+    pub content_type: Vec<u8>,
+}
+
+/// Build identification returned by each contract's `contract_info`
+/// message, so explorers and support staff can tell exactly which build
+/// a deployed address is running without probing code hashes.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ContractInfo {
+    /// The contract crate's semantic version at build time.
+    pub version: String,
+    /// The source commit the contract was built from, baked in by the
+    /// crate's build script; `unknown` for builds outside a git
+    /// checkout.
+    pub commit: String,
+    /// The cargo features the build was compiled with, beyond the
+    /// defaults.
+    pub features: Vec<String>,
+}
+
+/// An explicit weight budget an outbound cross-contract call is
+/// dispatched under. Contracts store one per integration so operators
+/// can retune an allotment — a heavier strategy, a leaner hook —
+/// without a code upgrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct CallBudget {
+    /// Maximum ref-time the callee may consume.
+    pub ref_time: u64,
+    /// Maximum proof size the call may occupy.
+    pub proof_size: u64,
+}
+
+/// Why a cross-contract call failed below the application level,
+/// shared by every contract that dispatches one (round to NFT, round
+/// to strategy, factory to round) so callers and UIs can tell
+/// retryable failures from permanent ones without parsing free-form
+/// errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CallFailure {
+    /// The callee executed and refused with its own error type, which
+    /// the caller does not carry in detail. Permanent until the
+    /// callee's state or configuration changes.
+    Module,
+    /// The environment could not complete the call: the callee
+    /// trapped, the account has no code, or the value transfer failed.
+    /// Under pallet-contracts an exhausted weight budget also surfaces
+    /// here, as a trap in the callee.
+    Environment,
+    /// The call ran out of the weight allotted to it, on environments
+    /// that distinguish exhaustion from a trap. Retryable with a
+    /// larger budget.
+    OutOfGas,
+    /// The callee's dispatcher could not read the input, or its reply
+    /// did not decode as the expected type: the two sides disagree
+    /// about the ABI. Permanent until one of them is upgraded.
+    Decode,
+}
+
+impl CallFailure {
+    /// Classifies an environment-level call error. Pallet-contracts
+    /// reports an exhausted weight budget as a trap in the callee, so
+    /// this never yields [`CallFailure::OutOfGas`]; the variant exists
+    /// for environments that can tell the two apart.
+    pub fn from_env(error: ink::env::Error) -> Self {
+        match error {
+            ink::env::Error::Decode(_) => CallFailure::Decode,
+            _ => CallFailure::Environment,
+        }
+    }
+}
+
+/// Errors a `Mintable` implementation may return from a mint. Rounds
+/// decode this from the acknowledgement contract's reply and surface it
+/// verbatim, so the encoding is shared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum MintError {
+    /// The caller is not authorized to mint.
+    NotMinter,
+    /// A token with the derived id already exists.
+    TokenExists,
+    /// The recipient may not receive tokens.
+    NotAllowed,
+    /// The token could not be recorded.
+    CannotInsert,
+    /// The fragment cid is empty or exceeds the implementation's bound.
+    InvalidCid,
+}
+
+/// Errors a `Burnable` implementation may return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BurnError {
+    /// The token does not exist.
+    TokenNotFound,
+    /// The caller may not burn this token.
+    NotAllowed,
+}
@@ -0,0 +1,284 @@
+//! Merkle mountain range primitives used to commit to a round's fragment set.
+//!
+//! A round publisher builds an MMR over the digests of every fragment in the
+//! round (see the `mmr-builder` tooling crate) and stores only its root
+//! on-chain. Claimers then submit membership proofs against that root. Both
+//! sides hash through this one module, so the constructions cannot drift.
+
+use ckb_merkle_mountain_range::{Merge, MerkleProof, Result as MmrResult};
+use core::marker::PhantomData;
+use ink::prelude::vec::Vec;
+
+/// Domain-separation byte prefixed when hashing a leaf. Distinct from
+/// [`NODE_DOMAIN`] so submitted bytes equal to a concatenated child pair
+/// cannot be passed off as a leaf (a second-preimage trick).
+pub const LEAF_DOMAIN: u8 = 0x00;
+
+/// Domain-separation byte prefixed when hashing an interior node.
+pub const NODE_DOMAIN: u8 = 0x01;
+
+/// Hashes `input` with the MMR's digest function.
+///
+/// By default this is Keccak-256 through the `hash_bytes` host function,
+/// so on-chain verification costs a host call instead of executing a
+/// Wasm-compiled hash and the round contract no longer links the `sha3`
+/// crate. Rounds whose roots were built with the original in-Wasm
+/// SHA3-256 construction enable the `legacy-sha3` feature, which keeps
+/// hashing exactly as before; the two backends produce incompatible
+/// roots, so the feature must match the deployment being targeted.
+fn digest(input: &[u8]) -> Vec<u8> {
+    #[cfg(feature = "legacy-sha3")]
+    {
+        use sha3::Digest;
+        sha3::Sha3_256::digest(input).to_vec()
+    }
+    #[cfg(not(feature = "legacy-sha3"))]
+    {
+        let mut output = [0u8; 32];
+        ink::env::hash_bytes::<ink::env::hash::Keccak256>(input, &mut output);
+        output.to_vec()
+    }
+}
+
+/// A leaf of the fragment MMR: the domain-prefixed digest (see
+/// [`digest`] for the backend) of a fragment's bytes followed by its
+/// size in little-endian bytes, so the root commits to
+/// `(fragment_hash, fragment_size)` and a claimer cannot misstate how
+/// many bytes a fragment obliges them to store.
+#[derive(Debug, Default, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Leaf(pub Vec<u8>);
+
+impl Leaf {
+    /// Hashes `data` and the claimed `size` into a leaf. The round
+    /// contract passes the size it registered for the fragment, so a
+    /// proof only verifies when the publisher committed to that size.
+    pub fn from_parts(data: &[u8], size: u64) -> Self {
+        let mut input = Vec::with_capacity(data.len().saturating_add(9));
+        input.push(LEAF_DOMAIN);
+        input.extend_from_slice(data);
+        input.extend_from_slice(&size.to_le_bytes());
+        Leaf(digest(&input))
+    }
+}
+
+impl From<Vec<u8>> for Leaf {
+    /// Hashes `data` as a leaf of its own length, for builders holding
+    /// the full fragment bytes.
+    fn from(data: Vec<u8>) -> Self {
+        Self::from_parts(&data, data.len() as u64)
+    }
+}
+
+/// Merge strategy hashing the domain-prefixed concatenation of the two
+/// child digests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeLeaves;
+
+impl Merge for MergeLeaves {
+    type Item = Leaf;
+
+    fn merge(lhs: &Self::Item, rhs: &Self::Item) -> MmrResult<Self::Item> {
+        let mut input = Vec::with_capacity(lhs.0.len().saturating_add(rhs.0.len()).saturating_add(1));
+        input.push(NODE_DOMAIN);
+        input.extend_from_slice(&lhs.0);
+        input.extend_from_slice(&rhs.0);
+        Ok(Leaf(digest(&input)))
+    }
+}
+
+/// A SCALE-encodable membership proof against an MMR root.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo),
+    scale_info(skip_type_params(M))
+)]
+pub struct Proof<T, M> {
+    mmr_size: u64,
+    proof: Vec<T>,
+    _merge: PhantomData<M>,
+}
+
+impl<T, M> Default for Proof<T, M> {
+    fn default() -> Self {
+        Self {
+            mmr_size: 0,
+            proof: Vec::new(),
+            _merge: PhantomData,
+        }
+    }
+}
+
+impl<T, M> Proof<T, M> {
+    /// Builds a proof from an MMR size and its sibling items, so SDKs
+    /// and tests do not have to hand-craft the SCALE encoding.
+    pub fn new(mmr_size: u64, proof: Vec<T>) -> Self {
+        Self {
+            mmr_size,
+            proof,
+            _merge: PhantomData,
+        }
+    }
+
+    /// The size of the MMR the proof was generated against.
+    pub fn mmr_size(&self) -> u64 {
+        self.mmr_size
+    }
+
+    /// The sibling items the proof carries.
+    pub fn items(&self) -> &[T] {
+        &self.proof
+    }
+
+    /// Number of sibling items the proof carries, so verifiers can
+    /// bound their work before hashing anything.
+    pub fn len(&self) -> usize {
+        self.proof.len()
+    }
+
+    /// Whether the proof carries no items.
+    pub fn is_empty(&self) -> bool {
+        self.proof.is_empty()
+    }
+}
+
+impl<M> From<(u64, &[Vec<u8>])> for Proof<Leaf, M> {
+    /// Builds a proof from an MMR size and the raw sibling digests, as
+    /// off-chain provers and RPC layers usually carry them. The bytes
+    /// are wrapped as [`Leaf`] digests verbatim, not rehashed.
+    fn from((mmr_size, digests): (u64, &[Vec<u8>])) -> Self {
+        Self::new(mmr_size, digests.iter().cloned().map(Leaf).collect())
+    }
+}
+
+impl<T, M> Proof<T, M>
+where
+    T: Clone + PartialEq + core::fmt::Debug,
+    M: Merge<Item = T>,
+{
+    /// Verifies that the positioned `leaves` are members of the MMR
+    /// committed to by `root`.
+    pub fn verify(&self, root: T, leaves: Vec<(u64, T)>) -> bool {
+        MerkleProof::<T, M>::new(self.mmr_size, self.proof.clone())
+            .verify(root, leaves)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ckb_merkle_mountain_range::{util::MemStore, MMR};
+
+    fn build_mmr(leaf_count: u64) -> (Vec<Leaf>, Vec<u64>, Leaf, MemStore<Leaf>) {
+        let store = MemStore::default();
+        let mut mmr = MMR::<Leaf, MergeLeaves, &MemStore<Leaf>>::new(0, &store);
+        let mut leaves = Vec::new();
+        let mut positions = Vec::new();
+        for i in 0..leaf_count {
+            let leaf = Leaf::from(i.to_le_bytes().to_vec());
+            positions.push(mmr.push(leaf.clone()).expect("push works"));
+            leaves.push(leaf);
+        }
+        let root = mmr.get_root().expect("root exists");
+        mmr.commit().expect("commit works");
+        (leaves, positions, root, store)
+    }
+
+    #[test]
+    fn proof_round_trip_verifies() {
+        let (leaves, positions, root, store) = build_mmr(7);
+        let mmr = MMR::<Leaf, MergeLeaves, &MemStore<Leaf>>::new(
+            ckb_merkle_mountain_range::leaf_index_to_mmr_size(6),
+            &store,
+        );
+        let raw = mmr.gen_proof(vec![positions[3]]).expect("proof exists");
+        let proof =
+            Proof::<Leaf, MergeLeaves>::new(raw.mmr_size(), raw.proof_items().to_vec());
+        assert!(proof.verify(root.clone(), vec![(positions[3], leaves[3].clone())]));
+        // a different leaf at the same position must not verify
+        assert!(!proof.verify(root, vec![(positions[3], leaves[4].clone())]));
+    }
+
+    #[test]
+    fn constructed_proofs_round_trip_through_their_parts() {
+        let (leaves, positions, root, store) = build_mmr(5);
+        let mmr = MMR::<Leaf, MergeLeaves, &MemStore<Leaf>>::new(
+            ckb_merkle_mountain_range::leaf_index_to_mmr_size(4),
+            &store,
+        );
+        let raw = mmr.gen_proof(vec![positions[2]]).expect("proof exists");
+        let proof =
+            Proof::<Leaf, MergeLeaves>::new(raw.mmr_size(), raw.proof_items().to_vec());
+        assert_eq!(proof.mmr_size(), raw.mmr_size());
+        assert_eq!(proof.items(), raw.proof_items());
+        // the raw digests, as an SDK would carry them, build the same proof
+        let digests: Vec<Vec<u8>> = proof.items().iter().map(|leaf| leaf.0.clone()).collect();
+        let rebuilt = Proof::<Leaf, MergeLeaves>::from((proof.mmr_size(), &digests[..]));
+        assert_eq!(rebuilt, proof);
+        assert!(rebuilt.verify(root, vec![(positions[2], leaves[2].clone())]));
+    }
+
+    #[test]
+    fn empty_proof_rejects() {
+        let (leaves, positions, root, _store) = build_mmr(4);
+        let proof = Proof::<Leaf, MergeLeaves>::default();
+        assert!(!proof.verify(root, vec![(positions[0], leaves[0].clone())]));
+    }
+
+    #[test]
+    fn leaf_hashing_is_deterministic() {
+        let a = Leaf::from(b"fragment".to_vec());
+        let b = Leaf::from(b"fragment".to_vec());
+        assert_eq!(a, b);
+        assert_eq!(a.0.len(), 32);
+        assert_ne!(a, Leaf::from(b"other".to_vec()));
+    }
+
+    #[test]
+    fn leaf_commits_to_the_fragment_size() {
+        let data = b"fragment".to_vec();
+        assert_eq!(
+            Leaf::from(data.clone()),
+            Leaf::from_parts(&data, data.len() as u64)
+        );
+        // the same bytes under a misstated size hash to a different leaf
+        assert_ne!(
+            Leaf::from_parts(&data, data.len() as u64),
+            Leaf::from_parts(&data, data.len() as u64 + 1)
+        );
+    }
+
+    #[test]
+    fn leaf_digest_matches_the_active_backend() {
+        let leaf = Leaf::from(b"fragment".to_vec());
+        // `[LEAF_DOMAIN] || "fragment" || 8u64 LE` under each backend;
+        // regenerating either vector in another implementation confirms
+        // the construction, and the two differing is what makes the
+        // `legacy-sha3` feature load-bearing
+        #[cfg(feature = "legacy-sha3")]
+        let expected = [
+            21, 152, 42, 104, 187, 180, 71, 114, 76, 41, 36, 175, 186, 52, 125, 198, 140, 85,
+            123, 193, 105, 165, 144, 229, 164, 216, 2, 0, 83, 105, 133, 163,
+        ];
+        #[cfg(not(feature = "legacy-sha3"))]
+        let expected = [
+            101, 114, 137, 218, 85, 253, 230, 124, 107, 143, 38, 174, 115, 54, 30, 124, 240,
+            180, 50, 85, 182, 89, 105, 164, 239, 27, 249, 184, 38, 77, 195, 202,
+        ];
+        assert_eq!(leaf.0, expected);
+    }
+
+    #[test]
+    fn leaf_and_node_hashing_are_domain_separated() {
+        let lhs = Leaf::from(b"left".to_vec());
+        let rhs = Leaf::from(b"right".to_vec());
+        let node = MergeLeaves::merge(&lhs, &rhs).expect("merge works");
+        // the concatenated child digests, rehashed as a leaf, must not
+        // collide with the interior node
+        let mut concatenated = lhs.0.clone();
+        concatenated.extend_from_slice(&rhs.0);
+        assert_ne!(Leaf::from(concatenated), node);
+    }
+}
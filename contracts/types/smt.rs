@@ -0,0 +1,253 @@
+//! Sparse Merkle tree primitives used to commit to a round's revoked
+//! cid set.
+//!
+//! A round publisher maintains an off-chain tree of 2^256 slots, one
+//! per possible cid key, almost all of them empty. Revoking a cid sets
+//! its slot; the publisher stores only the root on-chain. Claimers then
+//! submit non-membership proofs — a Merkle path showing their cid's
+//! slot is still empty — so the revocation set can grow without
+//! rewriting the main fragment MMR. Both sides hash through this one
+//! module, so the constructions cannot drift.
+
+use ink::prelude::vec::Vec;
+
+/// Depth of the tree: one level per bit of a [`key`].
+pub const DEPTH: usize = 256;
+
+/// Domain-separation byte prefixed when hashing a slot. Distinct from
+/// the MMR's domains so a proof item can never be replayed between the
+/// two trees.
+pub const LEAF_DOMAIN: u8 = 0x02;
+
+/// Domain-separation byte prefixed when hashing an interior node.
+pub const NODE_DOMAIN: u8 = 0x03;
+
+/// Hashes `input` with the tree's digest function, Keccak-256 through
+/// the `hash_bytes` host function. The revocation tree is newer than
+/// the `legacy-sha3` deployments, so unlike the MMR it has no legacy
+/// backend.
+fn digest(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    ink::env::hash_bytes::<ink::env::hash::Keccak256>(input, &mut output);
+    output
+}
+
+/// Derives the tree key of a cid: its Keccak-256 digest, read as a
+/// path of [`DEPTH`] bits, most significant bit first from byte zero.
+/// Hashing spreads adversarially chosen cids uniformly over the key
+/// space, keeping proof sizes near their expectation.
+pub fn key(cid: &[u8]) -> [u8; 32] {
+    digest(cid)
+}
+
+/// The hash of an empty slot.
+pub fn empty_leaf() -> [u8; 32] {
+    digest(&[LEAF_DOMAIN])
+}
+
+/// The hash of the slot recording `key` as revoked. The key is bound
+/// into the leaf so a revocation cannot be relocated to another slot.
+pub fn revoked_leaf(key: &[u8; 32]) -> [u8; 32] {
+    let mut input = Vec::with_capacity(key.len().saturating_add(1));
+    input.push(LEAF_DOMAIN);
+    input.extend_from_slice(key);
+    digest(&input)
+}
+
+/// Hashes two child digests into their parent.
+pub fn merge(lhs: &[u8; 32], rhs: &[u8; 32]) -> [u8; 32] {
+    let mut input = Vec::with_capacity(lhs.len().saturating_add(rhs.len()).saturating_add(1));
+    input.push(NODE_DOMAIN);
+    input.extend_from_slice(lhs);
+    input.extend_from_slice(rhs);
+    digest(&input)
+}
+
+/// The root of the tree with no revocations, against which every
+/// cid has a trivial (all-default-siblings) non-membership proof.
+pub fn empty_root() -> [u8; 32] {
+    let mut node = empty_leaf();
+    for _ in 0..DEPTH {
+        node = merge(&node, &node);
+    }
+    node
+}
+
+/// Masks isolating each bit of a byte, most significant first, in the
+/// order [`key_bit`] counts them.
+const KEY_BIT_MASKS: [u8; 8] = [0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01];
+
+/// Masks isolating each bit of a byte, least significant first, in the
+/// order the proof bitmap counts them.
+const BITMAP_MASKS: [u8; 8] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80];
+
+/// Returns bit `index` of `key`, counted most significant first from
+/// byte zero; bit zero decides the branch at the root.
+fn key_bit(key: &[u8; 32], index: usize) -> bool {
+    key[index / 8] & KEY_BIT_MASKS[index % 8] != 0
+}
+
+/// A SCALE-encodable proof that a cid's slot is empty in the tree
+/// committed to by a root.
+///
+/// Siblings equal to the default (empty-subtree) hash at their level
+/// are elided: the bitmap marks which levels supply an explicit
+/// sibling, leaf level first, and `siblings` lists exactly those in the
+/// same order. With few revocations almost every sibling is default,
+/// so proofs stay a handful of hashes instead of [`DEPTH`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NonMembershipProof {
+    /// Bit `level` (byte `level / 8`, bit `level % 8` from the least
+    /// significant) set means level `level` above the leaf has an
+    /// explicit sibling in `siblings`.
+    pub bitmap: [u8; 32],
+    /// The non-default siblings, leaf level first.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl NonMembershipProof {
+    /// Verifies that `cid`'s slot is empty in the tree committed to by
+    /// `root`: folds the empty leaf up the cid's key path through the
+    /// supplied and default siblings and compares the result. Spare
+    /// siblings beyond the bitmap's count reject, so a proof cannot
+    /// smuggle unchecked bytes.
+    pub fn verify(&self, root: &[u8], cid: &[u8]) -> bool {
+        let key = key(cid);
+        let mut siblings = self.siblings.iter();
+        let mut current = empty_leaf();
+        let mut default = empty_leaf();
+        for (level, key_index) in (0..DEPTH).zip((0..DEPTH).rev()) {
+            let explicit = self.bitmap[level / 8] & BITMAP_MASKS[level % 8] != 0;
+            let sibling = if explicit {
+                match siblings.next() {
+                    Some(sibling) => *sibling,
+                    None => return false,
+                }
+            } else {
+                default
+            };
+            current = if key_bit(&key, key_index) {
+                merge(&sibling, &current)
+            } else {
+                merge(&current, &sibling)
+            };
+            default = merge(&default, &default);
+        }
+        siblings.next().is_none() && current[..] == *root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The default (all-empty) subtree hash at `height` levels above
+    /// the leaves.
+    fn default_at(height: usize) -> [u8; 32] {
+        let mut node = empty_leaf();
+        for _ in 0..height {
+            node = merge(&node, &node);
+        }
+        node
+    }
+
+    /// Root of the subtree `depth_from_root` levels below the root
+    /// whose slots hold exactly the given revoked keys.
+    fn subtree_root(keys: &[[u8; 32]], depth_from_root: usize) -> [u8; 32] {
+        if keys.is_empty() {
+            return default_at(DEPTH - depth_from_root);
+        }
+        if depth_from_root == DEPTH {
+            return revoked_leaf(&keys[0]);
+        }
+        let (right, left): (Vec<[u8; 32]>, Vec<[u8; 32]>) = keys
+            .iter()
+            .copied()
+            .partition(|key| key_bit(key, depth_from_root));
+        merge(
+            &subtree_root(&left, depth_from_root + 1),
+            &subtree_root(&right, depth_from_root + 1),
+        )
+    }
+
+    /// Builds the root committing to `revoked` cids.
+    fn root_of(revoked: &[&[u8]]) -> [u8; 32] {
+        let keys: Vec<[u8; 32]> = revoked.iter().map(|cid| key(cid)).collect();
+        subtree_root(&keys, 0)
+    }
+
+    /// Generates `cid`'s non-membership proof against the tree of
+    /// `revoked` cids, eliding default siblings as the verifier expects.
+    fn prove(revoked: &[&[u8]], cid: &[u8]) -> NonMembershipProof {
+        let keys: Vec<[u8; 32]> = revoked.iter().map(|cid| key(cid)).collect();
+        let target = key(cid);
+        let mut proof = NonMembershipProof::default();
+        // collect siblings root-down, then emit them leaf-up
+        let mut path = Vec::new();
+        let mut remaining = keys;
+        for depth in 0..DEPTH {
+            let (right, left): (Vec<[u8; 32]>, Vec<[u8; 32]>) = remaining
+                .iter()
+                .copied()
+                .partition(|key| key_bit(key, depth));
+            let (taken, sibling) = if key_bit(&target, depth) {
+                (right, subtree_root(&left, depth + 1))
+            } else {
+                (left, subtree_root(&right, depth + 1))
+            };
+            path.push(sibling);
+            remaining = taken;
+        }
+        for (level, sibling) in path.into_iter().rev().enumerate() {
+            if sibling != default_at(level) {
+                proof.bitmap[level / 8] |= 1 << (level % 8);
+                proof.siblings.push(sibling);
+            }
+        }
+        proof
+    }
+
+    #[test]
+    fn empty_tree_admits_every_cid() {
+        let root = empty_root();
+        assert_eq!(root, root_of(&[]));
+        let proof = NonMembershipProof::default();
+        assert!(proof.verify(&root, b"fragment-1"));
+        assert!(proof.verify(&root, b"fragment-2"));
+    }
+
+    #[test]
+    fn revoked_cids_have_no_proof_and_others_still_do() {
+        let revoked: &[&[u8]] = &[b"fragment-1", b"fragment-3"];
+        let root = root_of(revoked);
+        let proof = prove(revoked, b"fragment-2");
+        assert!(proof.verify(&root, b"fragment-2"));
+        // a revoked cid's own path folds to a non-empty slot
+        let without_target: &[&[u8]] = &[b"fragment-3"];
+        let stale = prove(without_target, b"fragment-1");
+        assert!(!stale.verify(&root, b"fragment-1"));
+    }
+
+    #[test]
+    fn proofs_do_not_transfer_between_roots() {
+        let revoked: &[&[u8]] = &[b"fragment-1"];
+        let grown: &[&[u8]] = &[b"fragment-1", b"fragment-3"];
+        let proof = prove(revoked, b"fragment-2");
+        assert!(proof.verify(&root_of(revoked), b"fragment-2"));
+        assert!(!proof.verify(&root_of(grown), b"fragment-2"));
+        assert!(!proof.verify(&empty_root(), b"fragment-2"));
+    }
+
+    #[test]
+    fn spare_or_missing_siblings_reject() {
+        let revoked: &[&[u8]] = &[b"fragment-1"];
+        let root = root_of(revoked);
+        let mut starved = prove(revoked, b"fragment-2");
+        starved.siblings.pop();
+        assert!(!starved.verify(&root, b"fragment-2"));
+        let mut padded = prove(revoked, b"fragment-2");
+        padded.siblings.push([0u8; 32]);
+        assert!(!padded.verify(&root, b"fragment-2"));
+    }
+}
@@ -0,0 +1,13 @@
+//! Sandboxed-runtime integration tests for the multi-contract flows —
+//! the factory deploying rounds, rounds minting acknowledgements
+//! cross-contract, reward payouts — that the per-contract `#[ink::test]`
+//! suites cannot reach, since the off-chain environment does not execute
+//! cross-contract calls or contract instantiation.
+//!
+//! The tests live in `tests/` and run against a drink! sandbox. The
+//! drink! test macro builds the contract bundles itself on first run;
+//! that build compiles the standard library for the on-chain target, so
+//! the toolchain needs the `rust-src` component (`rustup component add
+//! rust-src`). The crate is its own workspace — the drink! test macro
+//! needs a root package, which the virtual root manifest cannot
+//! provide — so run them with `cargo test` from `integration-tests/`.
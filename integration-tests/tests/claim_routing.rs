@@ -0,0 +1,178 @@
+//! Batched claims across contract boundaries: the router dispatching a
+//! mixed batch to a live round, crediting accepted claims to the
+//! submitter and reporting per-item outcomes without aborting.
+
+use std::error::Error;
+
+use drink::{
+    minimal::MinimalSandbox,
+    sandbox_api::balance_api::BalanceAPI,
+    session::{Session, NO_ARGS, NO_ENDOWMENT, NO_SALT},
+    AccountId32,
+};
+use fragments::fragments::Error as RoundError;
+use mmr_builder::MmrBuilder;
+use router::router::RouteResult;
+
+#[drink::contract_bundle_provider]
+enum BundleProvider {}
+
+/// The reward pool the test round is endowed with.
+const REWARD_POOL: u128 = 1_000_000_000;
+/// The per-claim base reward the test round is configured with.
+const REWARD_PER_CLAIM: u128 = 1_000_000;
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("0x");
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+/// The raw bytes of test fragment `n`, as committed in the MMR leaves.
+fn fragment_bytes(n: u8) -> Vec<u8> {
+    vec![n; 64]
+}
+
+/// The cid of test fragment `n`: raw-codec CIDv1-style bytes.
+fn cid(n: u8) -> Vec<u8> {
+    vec![0x01, 0x55, n]
+}
+
+/// Builds the MMR over `count` test fragments, returning the root and
+/// the transcoder literal of each fragment's manifest entry.
+fn build_manifest(count: u8) -> (MmrBuilder, Vec<u8>, String) {
+    let mut builder = MmrBuilder::new();
+    let mut entries = Vec::new();
+    for n in 0..count {
+        let bytes = fragment_bytes(n);
+        let size = bytes.len();
+        let position = builder.push(bytes).expect("push works");
+        entries.push(format!(
+            "Fragment {{ cid: {}, leaf_pos: {position}, release_block: 0, tier: Common, size: {size}, claim_deadline: None }}",
+            hex(&cid(n)),
+        ));
+    }
+    let root = builder.root().expect("root exists");
+    let manifest = format!("[{}]", entries.join(", "));
+    (builder, root, manifest)
+}
+
+/// The transcoder literal of the membership proof for the leaf at
+/// `position`.
+fn proof_literal(builder: &MmrBuilder, position: u64) -> String {
+    let proof = builder.gen_proof(position).expect("proof exists");
+    let items: Vec<String> = proof.items.iter().map(|item| hex(item)).collect();
+    format!(
+        "Proof {{ mmr_size: {}, proof: [{}] }}",
+        proof.mmr_size,
+        items.join(", ")
+    )
+}
+
+/// A funded account distinct from the sandbox's default actor.
+fn claimer(session: &mut Session<MinimalSandbox>) -> AccountId32 {
+    let account = AccountId32::new([2u8; 32]);
+    session
+        .sandbox()
+        .mint_into(&account, REWARD_POOL)
+        .expect("minting sandbox balance works");
+    account
+}
+
+/// The transcoder literal of the routed claim of fragment `n` against
+/// `round`.
+fn routed_claim(builder: &MmrBuilder, round: &AccountId32, n: u8) -> String {
+    let position = builder.positions()[n as usize];
+    format!(
+        "RoutedClaim {{ round: {}, proof: {}, cid: {}, hash: {} }}",
+        hex(round.as_ref()),
+        proof_literal(builder, position),
+        hex(&cid(n)),
+        hex(&fragment_bytes(n)),
+    )
+}
+
+/// A mixed batch through the router: the first claim is accepted and
+/// credited to the submitter, its duplicate is rejected by the round,
+/// and an item addressed to a non-round contract is unreachable — all
+/// without aborting the rest of the batch.
+#[drink::test]
+fn router_dispatches_a_mixed_batch(
+    mut session: Session<MinimalSandbox>,
+) -> Result<(), Box<dyn Error>> {
+    let fa_nft = session.deploy_bundle(
+        BundleProvider::FaNft.bundle()?,
+        "new",
+        NO_ARGS,
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    let (builder, root, manifest) = build_manifest(2);
+    let round = session.deploy_bundle(
+        BundleProvider::Fragments.bundle()?,
+        "with_fa_nft",
+        &[
+            "0".to_string(),
+            hex(&root),
+            manifest,
+            REWARD_PER_CLAIM.to_string(),
+            "LumpSum".to_string(),
+            hex(fa_nft.as_ref()),
+        ],
+        NO_SALT,
+        Some(REWARD_POOL),
+    )?;
+    let granted: Result<(), fa_nft::fa_nft::Error> = session.call_with_address(
+        fa_nft.clone(),
+        "set_minter",
+        &[hex(round.as_ref())],
+        NO_ENDOWMENT,
+    )??;
+    granted.expect("the collection owner grants minter rights");
+    let activated: Result<(), RoundError> =
+        session.call_with_address(round.clone(), "activate", NO_ARGS, NO_ENDOWMENT)??;
+    activated.expect("the deployer owns the round");
+
+    let router = session.deploy_bundle(
+        BundleProvider::Router.bundle()?,
+        "new",
+        NO_ARGS,
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    let bob = claimer(&mut session);
+    session.set_actor(bob.clone());
+    let batch = format!(
+        "[{}, {}, {}]",
+        routed_claim(&builder, &round, 0),
+        // the same claim again, for the round to reject
+        routed_claim(&builder, &round, 0),
+        // the collection is not a round, so this item is unreachable
+        routed_claim(&builder, &fa_nft, 1),
+    );
+    let results: Vec<RouteResult> = session.call_with_address(
+        router,
+        "route_claims",
+        &[batch],
+        NO_ENDOWMENT,
+    )??;
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[0], RouteResult::Minted(_)));
+    assert_eq!(results[1], RouteResult::Rejected(RoundError::AlreadyClaimed));
+    assert_eq!(results[2], RouteResult::Unreachable);
+
+    // the accepted claim was credited to the submitter, not the router
+    let claims: Vec<Vec<u8>> = session.call_with_address(
+        round,
+        "get_claims",
+        &[hex(bob.as_ref())],
+        NO_ENDOWMENT,
+    )??;
+    assert_eq!(claims, vec![cid(0)]);
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! The claim lifecycle across contract boundaries: a factory-deployed
+//! round minting acknowledgements and paying rewards, and a round
+//! minting into a pre-existing collection it was granted minter rights
+//! on.
+
+use std::error::Error;
+
+use drink::{
+    minimal::MinimalSandbox,
+    sandbox_api::balance_api::BalanceAPI,
+    session::{Session, NO_ARGS, NO_ENDOWMENT, NO_SALT},
+    AccountId32,
+};
+use factory::factory::Error as FactoryError;
+use fa_nft::fa_nft::TokenId;
+use fragments::fragments::Error as RoundError;
+use ink::primitives::AccountId;
+use mmr_builder::MmrBuilder;
+
+#[drink::contract_bundle_provider]
+enum BundleProvider {}
+
+/// The reward pool each test round is endowed with.
+const REWARD_POOL: u128 = 1_000_000_000;
+/// The per-claim base reward each test round is configured with.
+const REWARD_PER_CLAIM: u128 = 1_000_000;
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("0x");
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+/// The raw bytes of test fragment `n`; the MMR leaves commit to these
+/// directly, exactly as [`MmrBuilder::push`] hashes them.
+fn fragment_bytes(n: u8) -> Vec<u8> {
+    vec![n; 64]
+}
+
+/// The cid of test fragment `n`: raw-codec CIDv1-style bytes, as in the
+/// contract unit tests.
+fn cid(n: u8) -> Vec<u8> {
+    vec![0x01, 0x55, n]
+}
+
+/// Builds the MMR over `count` test fragments, returning the root and
+/// the transcoder literal of each fragment's manifest entry.
+fn build_manifest(count: u8) -> (MmrBuilder, Vec<u8>, String) {
+    let mut builder = MmrBuilder::new();
+    let mut entries = Vec::new();
+    for n in 0..count {
+        let bytes = fragment_bytes(n);
+        let size = bytes.len();
+        let position = builder.push(bytes).expect("push works");
+        entries.push(format!(
+            "Fragment {{ cid: {}, leaf_pos: {position}, release_block: 0, tier: Common, size: {size}, claim_deadline: None }}",
+            hex(&cid(n)),
+        ));
+    }
+    let root = builder.root().expect("root exists");
+    let manifest = format!("[{}]", entries.join(", "));
+    (builder, root, manifest)
+}
+
+/// The transcoder literal of the membership proof for the leaf at
+/// `position`.
+fn proof_literal(builder: &MmrBuilder, position: u64) -> String {
+    let proof = builder.gen_proof(position).expect("proof exists");
+    let items: Vec<String> = proof.items.iter().map(|item| hex(item)).collect();
+    format!(
+        "Proof {{ mmr_size: {}, proof: [{}] }}",
+        proof.mmr_size,
+        items.join(", ")
+    )
+}
+
+/// A funded account distinct from the sandbox's default actor.
+fn claimer(session: &mut Session<MinimalSandbox>) -> AccountId32 {
+    let account = AccountId32::new([2u8; 32]);
+    session
+        .sandbox()
+        .mint_into(&account, REWARD_POOL)
+        .expect("minting sandbox balance works");
+    account
+}
+
+fn claim_args(builder: &MmrBuilder, n: u8) -> Vec<String> {
+    let position = builder.positions()[n as usize];
+    vec![
+        proof_literal(builder, position),
+        hex(&cid(n)),
+        hex(&fragment_bytes(n)),
+        "None".to_string(),
+        "None".to_string(),
+        "None".to_string(),
+    ]
+}
+
+/// The full factory-driven flow: the factory deploys a round from the
+/// uploaded code hashes, the round instantiates its collection and takes
+/// minter rights during construction, a claimer proves a fragment and is
+/// minted to, collects the lump-sum reward, and cannot claim the same
+/// fragment twice. A failed cross-contract mint would fail the claim, so
+/// the successful claim also covers the round's minter rights.
+#[drink::test]
+fn factory_round_mints_and_rewards_claims(
+    mut session: Session<MinimalSandbox>,
+) -> Result<(), Box<dyn Error>> {
+    let fa_nft_hash = session.upload_bundle(BundleProvider::FaNft.bundle()?)?;
+    let round_hash = session.upload_bundle(BundleProvider::Fragments.bundle()?)?;
+    let factory = session.deploy_bundle(
+        BundleProvider::Factory.bundle()?,
+        "new",
+        &[hex(round_hash.as_ref()), hex(fa_nft_hash.as_ref())],
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    let (builder, root, manifest) = build_manifest(3);
+    let created: Result<AccountId, FactoryError> = session.call_with_address(
+        factory,
+        "create_round",
+        &[
+            hex(&root),
+            manifest,
+            REWARD_PER_CLAIM.to_string(),
+            "LumpSum".to_string(),
+            hex(&cid(0)),
+        ],
+        Some(REWARD_POOL),
+    )??;
+    let round = AccountId32::new(*created.expect("round deploys").as_ref());
+
+    // the factory handed the round to its caller, who opens it
+    let activated: Result<(), RoundError> =
+        session.call_with_address(round.clone(), "activate", NO_ARGS, NO_ENDOWMENT)??;
+    activated.expect("the publisher owns the round");
+
+    let bob = claimer(&mut session);
+    session.set_actor(bob.clone());
+    let claimed: Result<TokenId, RoundError> = session.call_with_address(
+        round.clone(),
+        "claim_fragment",
+        &claim_args(&builder, 0),
+        NO_ENDOWMENT,
+    )??;
+    claimed.expect("a valid proof claims the fragment");
+
+    let claims: Vec<Vec<u8>> = session.call_with_address(
+        round.clone(),
+        "get_claims",
+        &[hex(bob.as_ref())],
+        NO_ENDOWMENT,
+    )??;
+    assert_eq!(claims, vec![cid(0)]);
+
+    let reward: Result<u128, RoundError> =
+        session.call_with_address(round.clone(), "claim_reward", NO_ARGS, NO_ENDOWMENT)??;
+    assert!(reward.expect("one claim is rewarded") > 0);
+
+    let double: Result<TokenId, RoundError> = session.call_with_address(
+        round,
+        "claim_fragment",
+        &claim_args(&builder, 0),
+        NO_ENDOWMENT,
+    )??;
+    assert_eq!(double, Err(RoundError::AlreadyClaimed));
+    Ok(())
+}
+
+/// A round minting into a collection it did not instantiate: the
+/// collection owner grants it minter rights, and the acknowledgement
+/// token lands with the claimer.
+#[drink::test]
+fn round_mints_into_an_existing_collection(
+    mut session: Session<MinimalSandbox>,
+) -> Result<(), Box<dyn Error>> {
+    let fa_nft = session.deploy_bundle(
+        BundleProvider::FaNft.bundle()?,
+        "new",
+        NO_ARGS,
+        NO_SALT,
+        NO_ENDOWMENT,
+    )?;
+
+    let (builder, root, manifest) = build_manifest(2);
+    let round = session.deploy_bundle(
+        BundleProvider::Fragments.bundle()?,
+        "with_fa_nft",
+        &[
+            "0".to_string(),
+            hex(&root),
+            manifest,
+            REWARD_PER_CLAIM.to_string(),
+            "LumpSum".to_string(),
+            hex(fa_nft.as_ref()),
+        ],
+        NO_SALT,
+        Some(REWARD_POOL),
+    )?;
+
+    let granted: Result<(), fa_nft::fa_nft::Error> = session.call_with_address(
+        fa_nft.clone(),
+        "set_minter",
+        &[hex(round.as_ref())],
+        NO_ENDOWMENT,
+    )??;
+    granted.expect("the collection owner grants minter rights");
+    let activated: Result<(), RoundError> =
+        session.call_with_address(round.clone(), "activate", NO_ARGS, NO_ENDOWMENT)??;
+    activated.expect("the deployer owns the round");
+
+    let bob = claimer(&mut session);
+    session.set_actor(bob.clone());
+    let claimed: Result<TokenId, RoundError> = session.call_with_address(
+        round,
+        "claim_fragment",
+        &claim_args(&builder, 1),
+        NO_ENDOWMENT,
+    )??;
+    let token_id = claimed.expect("a valid proof claims the fragment");
+
+    let owner: Option<AccountId> = session.call_with_address(
+        fa_nft,
+        "owner_of",
+        &[token_id.to_string()],
+        NO_ENDOWMENT,
+    )??;
+    assert_eq!(owner, Some(AccountId::from(*bob.as_ref())));
+    Ok(())
+}
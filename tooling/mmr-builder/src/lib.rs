@@ -0,0 +1,150 @@
+//! Off-chain helper for constructing the merkle mountain range a
+//! `FragmentsRound` commits to.
+//!
+//! The leaf and merge hashing comes straight from the `fragments-types`
+//! crate the contract verifies with, so the two constructions cannot
+//! drift: a round publisher builds the MMR with this crate, stores the
+//! root on-chain, and hands out proofs the contract accepts.
+
+use ckb_merkle_mountain_range::{util::MemStore, Result as MmrResult, MMR};
+
+pub use fragments_types::mmr::{Leaf, MergeLeaves, LEAF_DOMAIN, NODE_DOMAIN};
+
+/// A membership proof for a single leaf, in the shape the round contract's
+/// `Proof` type SCALE-decodes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeafProof {
+    /// Size of the MMR the proof was generated against.
+    pub mmr_size: u64,
+    /// The proof items, root-ward digests from the leaf.
+    pub items: Vec<Vec<u8>>,
+    /// Position of the proven leaf in the MMR.
+    pub position: u64,
+}
+
+/// Builds the fragment MMR for a round and produces per-fragment proofs.
+pub struct MmrBuilder {
+    store: MemStore<Leaf>,
+    positions: Vec<u64>,
+}
+
+impl MmrBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self {
+            store: MemStore::default(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Appends a fragment's raw bytes, returning the position of its leaf.
+    pub fn push(&mut self, fragment: Vec<u8>) -> MmrResult<u64> {
+        let mut mmr = MMR::<Leaf, MergeLeaves, _>::new(self.mmr_size(), &self.store);
+        let position = mmr.push(Leaf::from(fragment))?;
+        mmr.commit()?;
+        self.positions.push(position);
+        Ok(position)
+    }
+
+    /// Returns the current root digest, to be stored in the round contract.
+    pub fn root(&self) -> MmrResult<Vec<u8>> {
+        let mmr = MMR::<Leaf, MergeLeaves, _>::new(self.mmr_size(), &self.store);
+        mmr.get_root().map(|leaf| leaf.0)
+    }
+
+    /// Generates the membership proof for the leaf at `position`.
+    pub fn gen_proof(&self, position: u64) -> MmrResult<LeafProof> {
+        let mmr = MMR::<Leaf, MergeLeaves, _>::new(self.mmr_size(), &self.store);
+        let proof = mmr.gen_proof(vec![position])?;
+        Ok(LeafProof {
+            mmr_size: proof.mmr_size(),
+            items: proof.proof_items().iter().map(|leaf| leaf.0.clone()).collect(),
+            position,
+        })
+    }
+
+    /// Returns the positions of every pushed leaf, in insertion order.
+    pub fn positions(&self) -> &[u64] {
+        &self.positions
+    }
+
+    fn mmr_size(&self) -> u64 {
+        if self.positions.is_empty() {
+            0
+        } else {
+            ckb_merkle_mountain_range::leaf_index_to_mmr_size((self.positions.len() - 1) as u64)
+        }
+    }
+}
+
+impl Default for MmrBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ckb_merkle_mountain_range::MerkleProof;
+
+    #[test]
+    fn root_and_proofs_verify() {
+        let mut builder = MmrBuilder::new();
+        let fragments: Vec<Vec<u8>> = (0u64..9).map(|i| i.to_le_bytes().to_vec()).collect();
+        for fragment in &fragments {
+            builder.push(fragment.clone()).expect("push works");
+        }
+        let root = Leaf(builder.root().expect("root exists"));
+        for (i, fragment) in fragments.iter().enumerate() {
+            let position = builder.positions()[i];
+            let proof = builder.gen_proof(position).expect("proof exists");
+            let verifier = MerkleProof::<Leaf, MergeLeaves>::new(
+                proof.mmr_size,
+                proof.items.into_iter().map(Leaf).collect(),
+            );
+            assert!(verifier
+                .verify(root.clone(), vec![(position, Leaf::from(fragment.clone()))])
+                .unwrap_or(false));
+        }
+    }
+
+    #[test]
+    fn proof_fails_for_wrong_fragment() {
+        let mut builder = MmrBuilder::new();
+        for i in 0u64..4 {
+            builder.push(i.to_le_bytes().to_vec()).expect("push works");
+        }
+        let root = Leaf(builder.root().expect("root exists"));
+        let position = builder.positions()[1];
+        let proof = builder.gen_proof(position).expect("proof exists");
+        let verifier = MerkleProof::<Leaf, MergeLeaves>::new(
+            proof.mmr_size,
+            proof.items.into_iter().map(Leaf).collect(),
+        );
+        assert!(!verifier
+            .verify(root, vec![(position, Leaf::from(b"bogus".to_vec()))])
+            .unwrap_or(false));
+    }
+
+    #[test]
+    fn proof_fails_for_misstated_size() {
+        let mut builder = MmrBuilder::new();
+        let fragment = b"sized-fragment".to_vec();
+        builder.push(fragment.clone()).expect("push works");
+        let root = Leaf(builder.root().expect("root exists"));
+        let position = builder.positions()[0];
+        let proof = builder.gen_proof(position).expect("proof exists");
+        let verifier = MerkleProof::<Leaf, MergeLeaves>::new(
+            proof.mmr_size,
+            proof.items.into_iter().map(Leaf).collect(),
+        );
+        // the right bytes under the wrong size commitment must not verify
+        assert!(!verifier
+            .verify(
+                root,
+                vec![(position, Leaf::from_parts(&fragment, fragment.len() as u64 + 1))],
+            )
+            .unwrap_or(false));
+    }
+}